@@ -10,6 +10,28 @@ pub enum GitHubResourceAddress {
     Repository { owner: String, repo: String },
     // #need(Doc, BranchProtection)
     BranchProtection { owner: String, repo: String, branch: String },
+    // #need(Doc, Branch)
+    Branch { owner: String, repo: String, branch: String },
+    // #need(Doc, BranchRule)
+    BranchRule { owner: String, repo: String, pattern: String },
+    // #need(Doc, CustomProperties)
+    CustomProperties { org: String },
+    // #need(Doc, ActionsPermissions)
+    ActionsPermissions { owner: String, repo: String },
+    // #need(Doc, OrgActionsPermissions)
+    OrgActionsPermissions { org: String },
+    // #need(Doc, ActionsSettings)
+    ActionsSettings { owner: String, repo: String },
+    // #need(Doc, RunnerGroup)
+    RunnerGroup { org: String, name: String },
+    // #need(Doc, Pages)
+    Pages { owner: String, repo: String },
+    // #need(Doc, CodeScanning)
+    CodeScanning { owner: String, repo: String },
+    // #need(Doc, InteractionLimits)
+    InteractionLimits { owner: String, repo: String },
+    // #need(Doc, OrgInteractionLimits)
+    OrgInteractionLimits { org: String },
 }
 
 impl ResourceAddress for GitHubResourceAddress {
@@ -20,24 +42,328 @@ impl ResourceAddress for GitHubResourceAddress {
             GitHubResourceAddress::BranchProtection { owner, repo, branch } => {
                 PathBuf::from(format!("github/{owner}/{repo}/branches/{branch}/protection.ron"))
             }
+            GitHubResourceAddress::Branch { owner, repo, branch } => {
+                PathBuf::from(format!("github/{owner}/{repo}/branches/{branch}/branch.ron"))
+            }
+            GitHubResourceAddress::BranchRule { owner, repo, pattern } => {
+                PathBuf::from(format!("github/{owner}/{repo}/branch_rules/{pattern}.ron"))
+            }
+            GitHubResourceAddress::CustomProperties { org } => PathBuf::from(format!("github/{org}/custom_properties.ron")),
+            GitHubResourceAddress::ActionsPermissions { owner, repo } => {
+                PathBuf::from(format!("github/{owner}/{repo}/actions/permissions.ron"))
+            }
+            GitHubResourceAddress::OrgActionsPermissions { org } => {
+                PathBuf::from(format!("github/{org}/actions/permissions.ron"))
+            }
+            GitHubResourceAddress::ActionsSettings { owner, repo } => {
+                PathBuf::from(format!("github/{owner}/{repo}/actions/settings.ron"))
+            }
+            GitHubResourceAddress::RunnerGroup { org, name } => {
+                PathBuf::from(format!("github/{org}/actions/runner_groups/{name}.ron"))
+            }
+            GitHubResourceAddress::Pages { owner, repo } => PathBuf::from(format!("github/{owner}/{repo}/pages.ron")),
+            GitHubResourceAddress::CodeScanning { owner, repo } => {
+                PathBuf::from(format!("github/{owner}/{repo}/code_scanning.ron"))
+            }
+            GitHubResourceAddress::InteractionLimits { owner, repo } => {
+                PathBuf::from(format!("github/{owner}/{repo}/interaction_limits.ron"))
+            }
+            GitHubResourceAddress::OrgInteractionLimits { org } => PathBuf::from(format!("github/{org}/interaction_limits.ron")),
         }
     }
 
     fn from_path(path: &Path) -> Result<Self, anyhow::Error> {
         let path_components: Vec<&str> = path.components().map(|s| s.as_os_str().to_str().unwrap()).collect();
 
-        match path_components[..] {
+        match path_components.as_slice() {
             ["github", "config.ron"] => Ok(GitHubResourceAddress::Config),
+            ["github", org, "custom_properties.ron"] => Ok(GitHubResourceAddress::CustomProperties { org: org.to_string() }),
+            ["github", org, "interaction_limits.ron"] => Ok(GitHubResourceAddress::OrgInteractionLimits { org: org.to_string() }),
+            ["github", org, "actions", "permissions.ron"] => {
+                Ok(GitHubResourceAddress::OrgActionsPermissions { org: org.to_string() })
+            }
+            // A repository named "actions" shares its directory with the organization's actions/ files, whose
+            // names don't clash with any repository-level path.
+            ["github", org, "actions", "runner_groups", file] => match file.strip_suffix(".ron") {
+                Some(name) if !name.is_empty() => Ok(GitHubResourceAddress::RunnerGroup {
+                    org: org.to_string(),
+                    name: name.to_string(),
+                }),
+                _ => Err(invalid_addr_path(path)),
+            },
+            ["github", owner, repo, "actions", "permissions.ron"] => Ok(GitHubResourceAddress::ActionsPermissions {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+            }),
+            ["github", owner, repo, "actions", "settings.ron"] => Ok(GitHubResourceAddress::ActionsSettings {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+            }),
             ["github", owner, repo, "repository.ron"] => Ok(GitHubResourceAddress::Repository {
                 owner: owner.to_string(),
                 repo: repo.to_string(),
             }),
-            ["github", owner, repo, "branches", branch, "protection.ron"] => Ok(GitHubResourceAddress::BranchProtection {
+            ["github", owner, repo, "pages.ron"] => Ok(GitHubResourceAddress::Pages {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+            }),
+            ["github", owner, repo, "interaction_limits.ron"] => Ok(GitHubResourceAddress::InteractionLimits {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+            }),
+            ["github", owner, repo, "code_scanning.ron"] => Ok(GitHubResourceAddress::CodeScanning {
                 owner: owner.to_string(),
                 repo: repo.to_string(),
-                branch: branch.to_string(),
             }),
+            // Branch names can contain slashes (e.g. release/1.x), so the branch spans every component in between.
+            ["github", owner, repo, "branches", branch @ .., "protection.ron"] if !branch.is_empty() => {
+                Ok(GitHubResourceAddress::BranchProtection {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    branch: branch.join("/"),
+                })
+            }
+            ["github", owner, repo, "branches", branch @ .., "branch.ron"] if !branch.is_empty() => {
+                Ok(GitHubResourceAddress::Branch {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    branch: branch.join("/"),
+                })
+            }
+            // Patterns like release/* span directories the same way.
+            ["github", owner, repo, "branch_rules", dirs @ .., file] if file.len() > 4 && file.ends_with(".ron") => {
+                let mut pattern: Vec<&str> = dirs.to_vec();
+                pattern.push(&file[..file.len() - ".ron".len()]);
+
+                Ok(GitHubResourceAddress::BranchRule {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    pattern: pattern.join("/"),
+                })
+            }
             _ => Err(invalid_addr_path(path)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip_branch(branch: &str) {
+        let addr = GitHubResourceAddress::BranchProtection {
+            owner: String::from("octo-org"),
+            repo: String::from("octo-repo"),
+            branch: branch.to_string(),
+        };
+
+        let path = addr.to_path_buf();
+        let GitHubResourceAddress::BranchProtection { owner, repo, branch: parsed } =
+            GitHubResourceAddress::from_path(&path).unwrap()
+        else {
+            panic!("{} didn't parse back as a branch protection", path.display());
+        };
+
+        assert_eq!(owner, "octo-org");
+        assert_eq!(repo, "octo-repo");
+        assert_eq!(parsed, branch);
+        assert_eq!(parsed_path(&path), path);
+    }
+
+    fn parsed_path(path: &Path) -> PathBuf {
+        GitHubResourceAddress::from_path(path).unwrap().to_path_buf()
+    }
+
+    fn parse(path: &str) -> GitHubResourceAddress {
+        GitHubResourceAddress::from_path(Path::new(path)).unwrap()
+    }
+
+    #[test]
+    fn branch_protection_round_trips() {
+        for branch in [
+            "main",
+            "release/1.x",
+            "feature/team/JIRA-123",
+            "v1.0.0",
+            "dependabot/npm_and_yarn/lodash-4.17.21",
+            "with space",
+            "percent%2Fslash",
+            "hash#and?query",
+            "ünïcödé",
+        ] {
+            round_trip_branch(branch);
+        }
+    }
+
+    #[test]
+    fn branch_named_like_a_file_round_trips() {
+        round_trip_branch("repository.ron");
+        round_trip_branch("branches/protection");
+    }
+
+    #[test]
+    fn empty_branch_is_rejected() {
+        assert!(GitHubResourceAddress::from_path(Path::new("github/octo-org/octo-repo/branches/protection.ron")).is_err());
+    }
+
+    #[test]
+    fn branch_round_trips() {
+        for branch in ["develop", "release/2025", "team/feature/x"] {
+            let addr = GitHubResourceAddress::Branch {
+                owner: String::from("octo-org"),
+                repo: String::from("octo-repo"),
+                branch: branch.to_string(),
+            };
+
+            let path = addr.to_path_buf();
+            let GitHubResourceAddress::Branch { branch: parsed, .. } = GitHubResourceAddress::from_path(&path).unwrap() else {
+                panic!("{} didn't parse back as a branch", path.display());
+            };
+
+            assert_eq!(parsed, branch);
+        }
+    }
+
+    #[test]
+    fn branch_rule_round_trips() {
+        for pattern in ["main", "release/*", "release/**/*", "v[0-9]*", "*", "feature/*/wip", "hotfix-?"] {
+            let addr = GitHubResourceAddress::BranchRule {
+                owner: String::from("octo-org"),
+                repo: String::from("octo-repo"),
+                pattern: pattern.to_string(),
+            };
+
+            let path = addr.to_path_buf();
+            let GitHubResourceAddress::BranchRule { pattern: parsed, .. } = GitHubResourceAddress::from_path(&path).unwrap()
+            else {
+                panic!("{} didn't parse back as a branch rule", path.display());
+            };
+
+            assert_eq!(parsed, pattern);
+            assert_eq!(parsed_path(&path), path);
+        }
+    }
+
+    #[test]
+    fn repository_and_config_round_trip() {
+        for path in ["github/config.ron", "github/octo-org/octo-repo/repository.ron"] {
+            assert_eq!(parsed_path(Path::new(path)), PathBuf::from(path));
+        }
+    }
+
+    #[test]
+    fn custom_properties_round_trip() {
+        let path = "github/octo-org/custom_properties.ron";
+        assert!(matches!(parse(path), GitHubResourceAddress::CustomProperties { org } if org == "octo-org"));
+        assert_eq!(parsed_path(Path::new(path)), PathBuf::from(path));
+    }
+
+    #[test]
+    fn actions_permissions_round_trip() {
+        let path = "github/octo-org/actions/permissions.ron";
+        assert!(matches!(parse(path), GitHubResourceAddress::OrgActionsPermissions { org } if org == "octo-org"));
+        assert_eq!(parsed_path(Path::new(path)), PathBuf::from(path));
+
+        // A repository named "actions" shares its directory with the organization's actions/ files.
+        for repo in ["octo-repo", "actions"] {
+            let path = format!("github/octo-org/{repo}/actions/permissions.ron");
+            assert!(matches!(
+                parse(&path),
+                GitHubResourceAddress::ActionsPermissions { owner, repo: parsed } if owner == "octo-org" && parsed == repo
+            ));
+            assert_eq!(parsed_path(Path::new(&path)), PathBuf::from(&path));
+        }
+    }
+
+    #[test]
+    fn actions_settings_round_trip() {
+        for repo in ["octo-repo", "actions"] {
+            let path = format!("github/octo-org/{repo}/actions/settings.ron");
+            assert!(matches!(
+                parse(&path),
+                GitHubResourceAddress::ActionsSettings { owner, repo: parsed } if owner == "octo-org" && parsed == repo
+            ));
+            assert_eq!(parsed_path(Path::new(&path)), PathBuf::from(&path));
+        }
+    }
+
+    #[test]
+    fn pages_round_trip() {
+        for repo in ["octo-repo", "actions"] {
+            let path = format!("github/octo-org/{repo}/pages.ron");
+            assert!(matches!(
+                parse(&path),
+                GitHubResourceAddress::Pages { owner, repo: parsed } if owner == "octo-org" && parsed == repo
+            ));
+            assert_eq!(parsed_path(Path::new(&path)), PathBuf::from(&path));
+        }
+    }
+
+    #[test]
+    fn code_scanning_round_trip() {
+        for repo in ["octo-repo", "actions"] {
+            let path = format!("github/octo-org/{repo}/code_scanning.ron");
+            assert!(matches!(
+                parse(&path),
+                GitHubResourceAddress::CodeScanning { owner, repo: parsed } if owner == "octo-org" && parsed == repo
+            ));
+            assert_eq!(parsed_path(Path::new(&path)), PathBuf::from(&path));
+        }
+    }
+
+    #[test]
+    fn runner_group_round_trips() {
+        for name in ["Default", "linux-builders", "gpu.large", "group.ron"] {
+            let addr = GitHubResourceAddress::RunnerGroup {
+                org: String::from("octo-org"),
+                name: name.to_string(),
+            };
+
+            let path = addr.to_path_buf();
+            let GitHubResourceAddress::RunnerGroup { org, name: parsed } = GitHubResourceAddress::from_path(&path).unwrap()
+            else {
+                panic!("{} didn't parse back as a runner group", path.display());
+            };
+
+            assert_eq!(org, "octo-org");
+            assert_eq!(parsed, name);
+            assert_eq!(parsed_path(&path), path);
+        }
+
+        assert!(GitHubResourceAddress::from_path(Path::new("github/octo-org/actions/runner_groups/.ron")).is_err());
+        assert!(GitHubResourceAddress::from_path(Path::new("github/octo-org/actions/runner_groups/linux")).is_err());
+    }
+
+    #[test]
+    fn org_actions_files_beside_a_repository_named_actions() {
+        // The organization's actions/ files live in the same directory as a repository named "actions".
+        assert!(matches!(
+            GitHubResourceAddress::from_path(Path::new("github/octo-org/actions/permissions.ron")).unwrap(),
+            GitHubResourceAddress::OrgActionsPermissions { .. }
+        ));
+        assert!(matches!(
+            GitHubResourceAddress::from_path(Path::new("github/octo-org/actions/runner_groups/linux.ron")).unwrap(),
+            GitHubResourceAddress::RunnerGroup { .. }
+        ));
+        assert!(matches!(
+            GitHubResourceAddress::from_path(Path::new("github/octo-org/actions/actions/permissions.ron")).unwrap(),
+            GitHubResourceAddress::ActionsPermissions { repo, .. } if repo == "actions"
+        ));
+    }
+
+    #[test]
+    fn interaction_limits_round_trip() {
+        let path = "github/octo-org/interaction_limits.ron";
+        assert!(matches!(parse(path), GitHubResourceAddress::OrgInteractionLimits { org } if org == "octo-org"));
+        assert_eq!(parsed_path(Path::new(path)), PathBuf::from(path));
+
+        for repo in ["octo-repo", "actions"] {
+            let path = format!("github/octo-org/{repo}/interaction_limits.ron");
+            assert!(matches!(
+                parse(&path),
+                GitHubResourceAddress::InteractionLimits { owner, repo: parsed } if owner == "octo-org" && parsed == repo
+            ));
+            assert_eq!(parsed_path(Path::new(&path)), PathBuf::from(&path));
+        }
+    }
+}
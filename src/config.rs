@@ -3,7 +3,10 @@ use autoschematic_macros::FieldTypes;
 use documented::{Documented, DocumentedFields};
 use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 // #[derive(Debug, Serialize, Deserialize, Clone)]
 // pub enum GithubRateLimitStrategy {
@@ -17,6 +20,18 @@ pub enum GithubRepositoryOwner {
     Organization(String),
 }
 
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize, Documented, Clone, Copy)]
+/// What to do when a repository's repository.ron is deleted.
+pub enum DeletionPolicy {
+    /// Refuse to plan the deletion; the plan fails with an error.
+    Forbid,
+    /// Archive the repository and mark it with a topic instead of deleting it.
+    Archive,
+    /// Delete the repository.
+    #[default]
+    Allow,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Documented, DocumentedFields, Clone, FieldTypes)]
 #[serde(deny_unknown_fields)]
 /// The primary configuration block for the GithubConnector.
@@ -29,6 +44,39 @@ pub struct GitHubConnectorConfig {
     pub enterprise_url: Option<String>,
     /// The number of requests to make in parallel. Defaults to 5.
     pub concurrent_requests: usize,
+    /// What to do when a repository.ron is deleted: Forbid, Archive or Allow. Defaults to Allow.
+    #[serde(default)]
+    pub deletion_policy: DeletionPolicy,
+    /// Per-repository overrides for deletion_policy, keyed by "owner/repo".
+    #[serde(default)]
+    pub repository_deletion_policies: HashMap<String, DeletionPolicy>,
+    /// The directory that repositories are backed up to before they're deleted, ideally outside the
+    /// workspace. Relative paths are resolved against the workspace. Defaults to .github-backups in the workspace.
+    #[serde(default = "default_backup_dir")]
+    pub backup_dir: PathBuf,
+    /// Whether to re-fetch each resource before executing its ops and abort if it changed since the plan.
+    /// Destructive ops are always checked. Defaults to true.
+    #[serde(default = "default_drift_check")]
+    pub drift_check: bool,
+    /// Per-resource overrides for drift_check, keyed by the resource's path, e.g. "github/owner/repo/repository.ron".
+    #[serde(default)]
+    pub resource_drift_checks: HashMap<String, bool>,
+    /// How many seconds to wait for code scanning default setup to finish configuring before reporting it
+    /// as still in progress. Defaults to 600.
+    #[serde(default = "default_code_scanning_timeout_secs")]
+    pub code_scanning_timeout_secs: u64,
+}
+
+fn default_backup_dir() -> PathBuf {
+    PathBuf::from(".github-backups")
+}
+
+fn default_drift_check() -> bool {
+    true
+}
+
+fn default_code_scanning_timeout_secs() -> u64 {
+    600
 }
 
 impl Default for GitHubConnectorConfig {
@@ -38,6 +86,12 @@ impl Default for GitHubConnectorConfig {
             users: Vec::new(),
             enterprise_url: None,
             concurrent_requests: 5,
+            deletion_policy: DeletionPolicy::default(),
+            repository_deletion_policies: HashMap::new(),
+            backup_dir: default_backup_dir(),
+            drift_check: default_drift_check(),
+            resource_drift_checks: HashMap::new(),
+            code_scanning_timeout_secs: default_code_scanning_timeout_secs(),
         }
     }
 }
@@ -54,6 +108,19 @@ impl GitHubConnectorConfig {
         let config: GitHubConnectorConfig = RON.from_str(&config_str)?;
         Ok(Some(config))
     }
+
+    /// The deletion policy for `owner/repo`, taking per-repository overrides into account.
+    pub fn deletion_policy_for(&self, owner: &str, repo: &str) -> DeletionPolicy {
+        self.repository_deletion_policies
+            .get(&format!("{}/{}", owner, repo))
+            .copied()
+            .unwrap_or(self.deletion_policy)
+    }
+
+    /// The per-resource override of drift_check for the resource at `path`, if there is one.
+    pub fn drift_check_for(&self, path: &Path) -> Option<bool> {
+        self.resource_drift_checks.get(&*path.to_string_lossy()).copied()
+    }
 }
 
 impl Resource for GitHubConnectorConfig {
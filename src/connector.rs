@@ -1,10 +1,13 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     path::{Path, PathBuf},
     sync::Arc,
 };
 
-use crate::resource::{self, BranchProtection, GitHubRepository};
+use crate::resource::{
+    self, ActionsPermissions, ActionsSettings, Branch, BranchProtection, CodeScanningDefaultSetup, CustomProperties,
+    GitHubRepository, InteractionLimits, OrgActionsPermissions, Pages, RunnerGroup,
+};
 use crate::{addr::GitHubResourceAddress, client::get_client};
 use crate::{
     config::GitHubConnectorConfig,
@@ -18,15 +21,19 @@ use autoschematic_core::{
     },
     diag::DiagnosticResponse,
     doc_dispatch, skeleton,
-    util::{ron_check_eq, ron_check_syntax},
+    util::{RON, ron_check_eq, ron_check_syntax},
 };
 use octocrab::Octocrab;
 use tokio::sync::{RwLock, Semaphore};
 
+pub mod backup;
+pub mod diag;
+pub mod drift;
 pub mod get;
 pub mod list;
 pub mod op_exec;
 pub mod plan;
+pub mod workspace;
 
 // #[derive(Default)]
 pub struct GitHubConnector {
@@ -143,7 +150,8 @@ impl Connector for GitHubConnector {
                 default_branch: String::from("main"),
                 archived: false,
                 disabled: false,
-                collaborators: collaborators
+                collaborators: collaborators,
+                ..Default::default()
             })
         ));
 
@@ -157,6 +165,11 @@ impl Connector for GitHubConnector {
                 required_status_checks: Some(resource::RequiredStatusChecks {
                     strict: true,
                     contexts: vec![String::from("ci/tests")],
+                    checks: vec![resource::StatusCheck {
+                        context: String::from("build"),
+                        app_id: None,
+                        app: Some(String::from("github-actions")),
+                    }],
                 }),
                 enforce_admins: true,
                 required_pull_request_reviews: Some(resource::PullRequestReviewEnforcement {
@@ -164,6 +177,16 @@ impl Connector for GitHubConnector {
                     dismiss_stale_reviews: true,
                     require_code_owner_reviews: false,
                     require_last_push_approval: false,
+                    dismissal_restrictions: Some(resource::ActorAllowances {
+                        users: Vec::new(),
+                        teams: vec![String::from("leads")],
+                        apps: Vec::new(),
+                    }),
+                    bypass_pull_request_allowances: Some(resource::ActorAllowances {
+                        users: Vec::new(),
+                        teams: Vec::new(),
+                        apps: vec![String::from("release-bot")],
+                    }),
                 }),
                 restrictions: None,
                 required_linear_history: false,
@@ -173,6 +196,189 @@ impl Connector for GitHubConnector {
                 required_conversation_resolution: true,
                 lock_branch: false,
                 allow_fork_syncing: true,
+                required_signatures: false,
+            })
+        ));
+
+        res.push(skeleton!(
+            GitHubResourceAddress::Branch {
+                owner: String::from("[owner]"),
+                repo: String::from("[repo_name]"),
+                branch: String::from("develop"),
+            },
+            resource::GitHubResource::Branch(resource::Branch {
+                source: String::from("main"),
+            })
+        ));
+
+        res.push(skeleton!(
+            GitHubResourceAddress::BranchRule {
+                owner: String::from("[owner]"),
+                repo: String::from("[repo_name]"),
+                pattern: String::from("release/*"),
+            },
+            resource::GitHubResource::BranchProtection(resource::BranchProtection {
+                required_status_checks: None,
+                enforce_admins: false,
+                required_pull_request_reviews: Some(resource::PullRequestReviewEnforcement {
+                    required_approving_review_count: 2,
+                    dismiss_stale_reviews: true,
+                    require_code_owner_reviews: true,
+                    require_last_push_approval: false,
+                    dismissal_restrictions: None,
+                    bypass_pull_request_allowances: None,
+                }),
+                restrictions: None,
+                required_linear_history: true,
+                allow_force_pushes: false,
+                allow_deletions: false,
+                block_creations: false,
+                required_conversation_resolution: false,
+                lock_branch: false,
+                allow_fork_syncing: false,
+                required_signatures: true,
+            })
+        ));
+
+        res.push(skeleton!(
+            GitHubResourceAddress::CustomProperties {
+                org: String::from("[org]"),
+            },
+            resource::GitHubResource::CustomProperties(resource::CustomProperties {
+                properties: BTreeMap::from([
+                    (
+                        String::from("owner"),
+                        resource::CustomProperty {
+                            value_type: resource::CustomPropertyType::String,
+                            required: false,
+                            default_value: None,
+                            description: Some(String::from("The team that owns the repository")),
+                            allowed_values: Vec::new(),
+                        },
+                    ),
+                    (
+                        String::from("tier"),
+                        resource::CustomProperty {
+                            value_type: resource::CustomPropertyType::SingleSelect,
+                            required: true,
+                            default_value: Some(resource::CustomPropertyValue::Single(String::from("3"))),
+                            description: Some(String::from("How critical the repository is")),
+                            allowed_values: vec![String::from("1"), String::from("2"), String::from("3")],
+                        },
+                    ),
+                ]),
+            })
+        ));
+
+        res.push(skeleton!(
+            GitHubResourceAddress::ActionsPermissions {
+                owner: String::from("[owner]"),
+                repo: String::from("[repo_name]"),
+            },
+            resource::GitHubResource::ActionsPermissions(resource::ActionsPermissions {
+                enabled: true,
+                allowed_actions: resource::AllowedActions::Selected,
+                selected_actions: Some(resource::SelectedActions {
+                    github_owned_allowed: true,
+                    verified_allowed: false,
+                    patterns_allowed: vec![String::from("docker/*")],
+                }),
+                workflow_permissions: resource::WorkflowPermissions {
+                    default_workflow_permissions: resource::WorkflowTokenPermissions::Read,
+                    can_approve_pull_request_reviews: false,
+                },
+            })
+        ));
+
+        res.push(skeleton!(
+            GitHubResourceAddress::OrgActionsPermissions {
+                org: String::from("[org]"),
+            },
+            resource::GitHubResource::OrgActionsPermissions(resource::OrgActionsPermissions {
+                enabled_repositories: resource::EnabledRepositories::All,
+                allowed_actions: resource::AllowedActions::LocalOnly,
+                selected_actions: None,
+                workflow_permissions: resource::WorkflowPermissions {
+                    default_workflow_permissions: resource::WorkflowTokenPermissions::Read,
+                    can_approve_pull_request_reviews: false,
+                },
+            })
+        ));
+
+        res.push(skeleton!(
+            GitHubResourceAddress::ActionsSettings {
+                owner: String::from("[owner]"),
+                repo: String::from("[repo_name]"),
+            },
+            resource::GitHubResource::ActionsSettings(resource::ActionsSettings {
+                artifact_and_log_retention_days: 90,
+                fork_pr_approval: Some(resource::ForkPrApproval::FirstTimeContributors),
+                access_level: None,
+                oidc_subject_claim: resource::OidcSubjectClaim {
+                    use_default: false,
+                    include_claim_keys: vec![String::from("repo"), String::from("context")],
+                },
+            })
+        ));
+
+        res.push(skeleton!(
+            GitHubResourceAddress::Pages {
+                owner: String::from("[owner]"),
+                repo: String::from("[repo_name]"),
+            },
+            resource::GitHubResource::Pages(resource::Pages {
+                source: resource::PagesSource::Branch {
+                    branch: String::from("main"),
+                    path: String::from("/docs"),
+                },
+                custom_domain: Some(String::from("docs.example.com")),
+                https_enforced: true,
+                visibility: None,
+            })
+        ));
+
+        res.push(skeleton!(
+            GitHubResourceAddress::CodeScanning {
+                owner: String::from("[owner]"),
+                repo: String::from("[repo_name]"),
+            },
+            resource::GitHubResource::CodeScanning(resource::CodeScanningDefaultSetup {
+                state: resource::CodeScanningState::Configured,
+                query_suite: resource::QuerySuite::Extended,
+                languages: vec![String::from("actions"), String::from("python")],
+            })
+        ));
+
+        res.push(skeleton!(
+            GitHubResourceAddress::InteractionLimits {
+                owner: String::from("[owner]"),
+                repo: String::from("[repo_name]"),
+            },
+            resource::GitHubResource::InteractionLimits(resource::InteractionLimits {
+                limit: resource::InteractionLimit::CollaboratorsOnly,
+                expiry: resource::InteractionLimitExpiry::OneWeek,
+            })
+        ));
+
+        res.push(skeleton!(
+            GitHubResourceAddress::OrgInteractionLimits {
+                org: String::from("[org]"),
+            },
+            resource::GitHubResource::InteractionLimits(resource::InteractionLimits {
+                limit: resource::InteractionLimit::ExistingUsers,
+                expiry: resource::InteractionLimitExpiry::OneDay,
+            })
+        ));
+
+        res.push(skeleton!(
+            GitHubResourceAddress::RunnerGroup {
+                org: String::from("[org]"),
+                name: String::from("[group_name]"),
+            },
+            resource::GitHubResource::RunnerGroup(resource::RunnerGroup {
+                visibility: resource::RunnerGroupVisibility::Selected(vec![String::from("[repo_name]")]),
+                allows_public_repositories: false,
+                restricted_to_workflows: Some(vec![String::from("[org]/[repo_name]/.github/workflows/deploy.yml@main")]),
             })
         ));
 
@@ -184,8 +390,99 @@ impl Connector for GitHubConnector {
 
         match addr {
             GitHubResourceAddress::Config => ron_check_eq::<GitHubConnectorConfig>(a, b),
-            GitHubResourceAddress::Repository { .. } => ron_check_eq::<resource::GitHubRepository>(a, b),
-            GitHubResourceAddress::BranchProtection { .. } => ron_check_eq::<resource::BranchProtection>(a, b),
+            GitHubResourceAddress::Repository { .. } => {
+                let mut a: resource::GitHubRepository = RON.from_str(str::from_utf8(a)?)?;
+                let mut b: resource::GitHubRepository = RON.from_str(str::from_utf8(b)?)?;
+                let has_creation_options = a.has_creation_options() || b.has_creation_options();
+                a.clear_creation_options();
+                b.clear_creation_options();
+
+                // Left unequal so that the plan can note that the creation-only options are ignored.
+                Ok(a == b && !has_creation_options)
+            }
+            GitHubResourceAddress::BranchProtection { .. } => {
+                let mut a: resource::BranchProtection = RON.from_str(str::from_utf8(a)?)?;
+                let mut b: resource::BranchProtection = RON.from_str(str::from_utf8(b)?)?;
+                // Slugs are only resolved when planning, so a slug compares unequal to an app_id and the plan resolves it.
+                if let (Some(a_checks), Some(b_checks)) = (&mut a.required_status_checks, &mut b.required_status_checks) {
+                    a_checks.clear_unresolved_apps(b_checks);
+                }
+                Ok(a == b)
+            }
+            GitHubResourceAddress::BranchRule { .. } => ron_check_eq::<resource::BranchProtection>(a, b),
+            GitHubResourceAddress::CustomProperties { .. } => ron_check_eq::<resource::CustomProperties>(a, b),
+            GitHubResourceAddress::ActionsPermissions { .. } => {
+                let mut a: resource::ActionsPermissions = RON.from_str(str::from_utf8(a)?)?;
+                let mut b: resource::ActionsPermissions = RON.from_str(str::from_utf8(b)?)?;
+                a.clear_unused();
+                b.clear_unused();
+                Ok(a == b)
+            }
+            GitHubResourceAddress::OrgActionsPermissions { .. } => {
+                let mut a: resource::OrgActionsPermissions = RON.from_str(str::from_utf8(a)?)?;
+                let mut b: resource::OrgActionsPermissions = RON.from_str(str::from_utf8(b)?)?;
+                a.clear_unused();
+                b.clear_unused();
+                Ok(a == b)
+            }
+            GitHubResourceAddress::ActionsSettings { .. } => {
+                let mut a: resource::ActionsSettings = RON.from_str(str::from_utf8(a)?)?;
+                let mut b: resource::ActionsSettings = RON.from_str(str::from_utf8(b)?)?;
+                a.clear_unused();
+                b.clear_unused();
+                // Leaving the fork PR approval or access level out leaves it as it is, and each only
+                // reads back on the repositories it applies to.
+                if a.fork_pr_approval.is_none() || b.fork_pr_approval.is_none() {
+                    a.fork_pr_approval = None;
+                    b.fork_pr_approval = None;
+                }
+                if a.access_level.is_none() || b.access_level.is_none() {
+                    a.access_level = None;
+                    b.access_level = None;
+                }
+                Ok(a == b)
+            }
+            GitHubResourceAddress::Pages { .. } => {
+                let mut a: resource::Pages = RON.from_str(str::from_utf8(a)?)?;
+                let mut b: resource::Pages = RON.from_str(str::from_utf8(b)?)?;
+                // Leaving the visibility out leaves it as it is.
+                if a.visibility.is_none() || b.visibility.is_none() {
+                    a.visibility = None;
+                    b.visibility = None;
+                }
+                Ok(a == b)
+            }
+            GitHubResourceAddress::InteractionLimits { .. } | GitHubResourceAddress::OrgInteractionLimits { .. } => {
+                // The expiry can't be read back, so only the limit is compared.
+                let a: resource::InteractionLimits = RON.from_str(str::from_utf8(a)?)?;
+                let b: resource::InteractionLimits = RON.from_str(str::from_utf8(b)?)?;
+                Ok(a.limit == b.limit)
+            }
+            GitHubResourceAddress::CodeScanning { .. } => {
+                let mut a: resource::CodeScanningDefaultSetup = RON.from_str(str::from_utf8(a)?)?;
+                let mut b: resource::CodeScanningDefaultSetup = RON.from_str(str::from_utf8(b)?)?;
+                a.clear_unused();
+                b.clear_unused();
+                // With no languages given, GitHub picks them.
+                if a.languages.is_empty() || b.languages.is_empty() {
+                    a.languages.clear();
+                    b.languages.clear();
+                }
+                Ok(a == b)
+            }
+            GitHubResourceAddress::RunnerGroup { .. } => {
+                let mut a: resource::RunnerGroup = RON.from_str(str::from_utf8(a)?)?;
+                let mut b: resource::RunnerGroup = RON.from_str(str::from_utf8(b)?)?;
+                a.clear_unused();
+                b.clear_unused();
+                Ok(a == b)
+            }
+            GitHubResourceAddress::Branch { .. } => {
+                // The source only matters when the branch is created, so any two states of an existing branch are equal.
+                RON.from_str::<resource::Branch>(str::from_utf8(a)?)?;
+                RON.from_str::<resource::Branch>(str::from_utf8(b)?)?;
+                Ok(true)
+            }
         }
     }
 
@@ -195,14 +492,52 @@ impl Connector for GitHubConnector {
         match addr {
             GitHubResourceAddress::Config => ron_check_syntax::<GitHubConnectorConfig>(a),
             GitHubResourceAddress::Repository { .. } => ron_check_syntax::<resource::GitHubRepository>(a),
-            GitHubResourceAddress::BranchProtection { .. } => ron_check_syntax::<resource::BranchProtection>(a),
+            GitHubResourceAddress::Branch { .. } => ron_check_syntax::<resource::Branch>(a),
+            GitHubResourceAddress::CustomProperties { .. } => ron_check_syntax::<resource::CustomProperties>(a),
+            GitHubResourceAddress::ActionsPermissions { .. } => ron_check_syntax::<resource::ActionsPermissions>(a),
+            GitHubResourceAddress::OrgActionsPermissions { .. } => ron_check_syntax::<resource::OrgActionsPermissions>(a),
+            GitHubResourceAddress::ActionsSettings { .. } => ron_check_syntax::<resource::ActionsSettings>(a),
+            GitHubResourceAddress::RunnerGroup { .. } => ron_check_syntax::<resource::RunnerGroup>(a),
+            GitHubResourceAddress::Pages { .. } => ron_check_syntax::<resource::Pages>(a),
+            GitHubResourceAddress::CodeScanning { .. } => ron_check_syntax::<resource::CodeScanningDefaultSetup>(a),
+            GitHubResourceAddress::InteractionLimits { .. } | GitHubResourceAddress::OrgInteractionLimits { .. } => {
+                ron_check_syntax::<resource::InteractionLimits>(a)
+            }
+            GitHubResourceAddress::BranchProtection { .. } | GitHubResourceAddress::BranchRule { .. } => {
+                let syntax = ron_check_syntax::<resource::BranchProtection>(a)?;
+                if syntax.as_ref().is_some_and(|syntax| !syntax.diagnostics.is_empty()) {
+                    return Ok(syntax);
+                }
+
+                let text = str::from_utf8(a)?;
+                let protection: resource::BranchProtection = RON.from_str(text)?;
+                let warnings = diag::display_name_warnings(text, &protection);
+                if warnings.is_empty() {
+                    return Ok(syntax);
+                }
+
+                Ok(Some(DiagnosticResponse { diagnostics: warnings }))
+            }
         }
     }
 
     async fn get_docstring(&self, _addr: &Path, ident: DocIdent) -> Result<Option<GetDocResponse>, anyhow::Error> {
         doc_dispatch!(
             ident,
-            [GitHubConnectorConfig, GitHubRepository, BranchProtection,],
+            [
+                GitHubConnectorConfig,
+                GitHubRepository,
+                BranchProtection,
+                Branch,
+                CustomProperties,
+                ActionsPermissions,
+                OrgActionsPermissions,
+                ActionsSettings,
+                RunnerGroup,
+                Pages,
+                CodeScanningDefaultSetup,
+                InteractionLimits,
+            ],
             [CollaboratorPrincipal::User(String::new())]
         )
         // match ident {
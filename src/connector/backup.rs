@@ -0,0 +1,102 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, bail};
+use autoschematic_core::connector::ResourceAddress;
+use tokio::process::Command;
+
+use crate::{GitHubConnector, addr::GitHubResourceAddress};
+
+// Supplies GITHUB_TOKEN to git without putting it on the command line or in the mirror's config.
+const CREDENTIAL_HELPER: &str =
+    "credential.helper=!f() { echo username=x-access-token; echo \"password=$GITHUB_TOKEN\"; }; f";
+
+async fn run_git(args: &[&str], cwd: &Path) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .args(["-c", "credential.helper=", "-c", CREDENTIAL_HELPER])
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .await
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+impl GitHubConnector {
+    /// Back up a repository before it is deleted: a git bundle of all branches and tags, plus the
+    /// repository's settings as RON. Returns the directory the backup was written to.
+    pub async fn backup_repository(&self, owner: &str, repo: &str) -> anyhow::Result<PathBuf> {
+        let addr = GitHubResourceAddress::Repository {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        };
+
+        let Some(current) = self.do_get(&addr.to_path_buf()).await? else {
+            bail!("Repository {}/{} doesn't exist", owner, repo);
+        };
+
+        let client = self.client.read().await.clone();
+        let github_repo = client.repos(owner, repo).get().await?;
+        let Some(clone_url) = github_repo.clone_url else {
+            bail!("Repository {}/{} has no clone URL", owner, repo);
+        };
+
+        let backup_root = self.config.read().await.backup_dir.clone();
+        let backup_root = std::path::absolute(self.prefix.join(backup_root))?;
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let backup_dir = backup_root.join(owner).join(format!("{}-{}", repo, timestamp));
+        tokio::fs::create_dir_all(&backup_dir).await?;
+
+        // Keep backups written inside the workspace from being committed along with it.
+        if backup_root.starts_with(std::path::absolute(&self.prefix)?) {
+            let gitignore = backup_root.join(".gitignore");
+            if !gitignore.exists() {
+                tokio::fs::write(gitignore, "*\n").await?;
+            }
+        }
+
+        tokio::fs::write(backup_dir.join("repository.ron"), &current.resource_definition).await?;
+
+        let mirror_dir = backup_dir.join("mirror.git");
+        run_git(
+            &[
+                "clone",
+                "--mirror",
+                "--quiet",
+                clone_url.as_str(),
+                &*mirror_dir.to_string_lossy(),
+            ],
+            &backup_dir,
+        )
+        .await?;
+
+        // git refuses to bundle a repository with no refs, so an empty repository only gets its metadata.
+        let refs = run_git(&["for-each-ref", "--format=%(refname)"], &mirror_dir).await?;
+        if refs.trim().is_empty() {
+            tracing::info!("Repository {}/{} is empty, backing up its metadata only", owner, repo);
+        } else {
+            let bundle_path = backup_dir.join(format!("{}.bundle", repo));
+            run_git(
+                &["bundle", "create", "--quiet", &*bundle_path.to_string_lossy(), "--all"],
+                &mirror_dir,
+            )
+            .await?;
+        }
+
+        tokio::fs::remove_dir_all(&mirror_dir).await?;
+
+        Ok(backup_dir)
+    }
+}
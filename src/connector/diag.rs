@@ -0,0 +1,78 @@
+use autoschematic_core::diag::{Diagnostic, DiagnosticPosition, DiagnosticSeverity, DiagnosticSpan};
+
+use crate::resource;
+
+/// Team and app slugs are lowercase with no spaces, so anything else is probably a display name
+/// like "Core Team", which the branch protection API won't accept.
+fn looks_like_display_name(value: &str) -> bool {
+    value.chars().any(|c| c.is_whitespace() || c.is_uppercase())
+}
+
+/// The span of the first occurrence of `"value"` in the file, or the start of the file if it isn't found.
+fn span_of(text: &str, value: &str) -> DiagnosticSpan {
+    let quoted = format!("\"{}\"", value);
+    let Some(offset) = text.find(&quoted) else {
+        return DiagnosticSpan {
+            start: DiagnosticPosition { line: 1, col: 1 },
+            end: DiagnosticPosition { line: 1, col: 1 },
+        };
+    };
+
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let col = before.len() - before.rfind('\n').map(|i| i + 1).unwrap_or(0) + 1;
+
+    DiagnosticSpan {
+        start: DiagnosticPosition {
+            line: line as u32,
+            col: col as u32,
+        },
+        end: DiagnosticPosition {
+            line: line as u32,
+            col: (col + quoted.chars().count()) as u32,
+        },
+    }
+}
+
+/// Warn about team and app names in a branch protection that look like display names rather than slugs.
+pub fn display_name_warnings(text: &str, protection: &resource::BranchProtection) -> Vec<Diagnostic> {
+    let mut named = Vec::new();
+
+    if let Some(restrictions) = &protection.restrictions {
+        named.extend(restrictions.teams.iter().map(|team| ("team", team)));
+        named.extend(restrictions.apps.iter().map(|app| ("app", app)));
+    }
+
+    if let Some(reviews) = &protection.required_pull_request_reviews {
+        for allowances in [&reviews.dismissal_restrictions, &reviews.bypass_pull_request_allowances]
+            .into_iter()
+            .flatten()
+        {
+            named.extend(allowances.teams.iter().map(|team| ("team", team)));
+            named.extend(allowances.apps.iter().map(|app| ("app", app)));
+        }
+    }
+
+    if let Some(checks) = &protection.required_status_checks {
+        named.extend(
+            checks
+                .checks
+                .iter()
+                .filter_map(|check| check.app.as_ref())
+                .map(|app| ("app", app)),
+        );
+    }
+
+    named
+        .into_iter()
+        .filter(|(_, value)| looks_like_display_name(value))
+        .map(|(kind, value)| Diagnostic {
+            severity: DiagnosticSeverity::Warning as u8,
+            span: span_of(text, value),
+            message: format!(
+                "{:?} looks like a {} display name. Use the {}'s slug instead (lowercase, with hyphens in place of spaces).",
+                value, kind, kind
+            ),
+        })
+        .collect()
+}
@@ -0,0 +1,133 @@
+use std::fmt::Debug;
+
+use anyhow::bail;
+use autoschematic_core::{
+    connector::{ConnectorOp, PlanResponseElement, ResourceAddress},
+    util::RON,
+};
+
+use crate::{
+    GitHubConnector,
+    addr::GitHubResourceAddress,
+    op::{GitHubConnectorOp, PlannedState},
+    resource,
+};
+
+/// FNV-1a, which is stable across builds, unlike std's default hasher.
+fn fnv1a(data: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in data {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+fn fingerprint_of<T: Debug>(value: &T) -> String {
+    fnv1a(format!("{:?}", value).as_bytes())
+}
+
+/// Fingerprint a resource's state as returned by `do_get`. The state is parsed first so that
+/// formatting and map ordering don't affect the result.
+pub fn state_fingerprint(addr: &GitHubResourceAddress, state: Option<&[u8]>) -> anyhow::Result<String> {
+    let Some(state) = state else {
+        return Ok(fnv1a(b"absent"));
+    };
+    let state = str::from_utf8(state)?;
+
+    match addr {
+        GitHubResourceAddress::Config => Ok(fnv1a(state.as_bytes())),
+        GitHubResourceAddress::Repository { .. } => {
+            let mut repo: resource::GitHubRepository = RON.from_str(state)?;
+
+            let mut collaborators: Vec<String> = repo
+                .collaborators
+                .drain()
+                .map(|(principal, role)| format!("{:?}={:?}", principal, role))
+                .collect();
+            collaborators.sort();
+
+            Ok(fingerprint_of(&(repo, collaborators)))
+        }
+        GitHubResourceAddress::Branch { .. } => Ok(fingerprint_of(&RON.from_str::<resource::Branch>(state)?)),
+        GitHubResourceAddress::CustomProperties { .. } => Ok(fingerprint_of(&RON.from_str::<resource::CustomProperties>(state)?)),
+        GitHubResourceAddress::ActionsPermissions { .. } => {
+            Ok(fingerprint_of(&RON.from_str::<resource::ActionsPermissions>(state)?))
+        }
+        GitHubResourceAddress::OrgActionsPermissions { .. } => {
+            Ok(fingerprint_of(&RON.from_str::<resource::OrgActionsPermissions>(state)?))
+        }
+        GitHubResourceAddress::ActionsSettings { .. } => Ok(fingerprint_of(&RON.from_str::<resource::ActionsSettings>(state)?)),
+        GitHubResourceAddress::RunnerGroup { .. } => Ok(fingerprint_of(&RON.from_str::<resource::RunnerGroup>(state)?)),
+        GitHubResourceAddress::Pages { .. } => Ok(fingerprint_of(&RON.from_str::<resource::Pages>(state)?)),
+        GitHubResourceAddress::InteractionLimits { .. } | GitHubResourceAddress::OrgInteractionLimits { .. } => {
+            Ok(fingerprint_of(&RON.from_str::<resource::InteractionLimits>(state)?))
+        }
+        GitHubResourceAddress::CodeScanning { .. } => {
+            Ok(fingerprint_of(&RON.from_str::<resource::CodeScanningDefaultSetup>(state)?))
+        }
+        GitHubResourceAddress::BranchProtection { .. } | GitHubResourceAddress::BranchRule { .. } => {
+            Ok(fingerprint_of(&RON.from_str::<resource::BranchProtection>(state)?))
+        }
+    }
+}
+
+impl GitHubConnector {
+    /// Guard the first op in a plan with a fingerprint of the state it was planned from. The rest of
+    /// the plan runs straight after it, so one check covers the whole address.
+    pub async fn guard_plan(
+        &self,
+        addr: &GitHubResourceAddress,
+        current: Option<&[u8]>,
+        res: &mut [PlanResponseElement],
+    ) -> anyhow::Result<()> {
+        // Creates have nothing to drift from.
+        if current.is_none() {
+            return Ok(());
+        }
+
+        let mut destructive = false;
+        for element in res.iter() {
+            destructive |= GitHubConnectorOp::from_str(&element.op_definition)?.is_destructive();
+        }
+
+        let Some(first) = res.first_mut() else {
+            return Ok(());
+        };
+
+        let planned = PlannedState {
+            fingerprint: state_fingerprint(addr, current)?,
+            check: if destructive {
+                Some(true)
+            } else {
+                self.config.read().await.drift_check_for(&addr.to_path_buf())
+            },
+        };
+
+        let op = GitHubConnectorOp::from_str(&first.op_definition)?;
+        first.op_definition = GitHubConnectorOp::Guarded(planned, Box::new(op)).to_string()?;
+
+        Ok(())
+    }
+
+    /// Re-fetch the resource and bail if it no longer matches the state the op was planned from.
+    pub async fn check_drift(&self, addr: &GitHubResourceAddress, planned: &PlannedState) -> anyhow::Result<()> {
+        let enabled = planned.check.unwrap_or(self.config.read().await.drift_check);
+        if !enabled {
+            return Ok(());
+        }
+
+        let live = self.do_get(&addr.to_path_buf()).await?;
+        let fingerprint = state_fingerprint(addr, live.as_ref().map(|live| live.resource_definition.as_slice()))?;
+
+        if fingerprint != planned.fingerprint {
+            bail!(
+                "{} changed since this op was planned, so it was not applied. Re-run the plan to pick up the current state \
+                 (or turn off drift_check or this resource's entry in resource_drift_checks in github/config.ron to skip this check).",
+                addr.to_path_buf().display()
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,12 @@
 use crate::{
     GitHubConnector,
     addr::GitHubResourceAddress,
-    github_ext::{BranchProtectionExt, ListExt},
+    github_ext::{
+        ActionsPermissionsExt, ActionsScope, ActionsSettingsExt, BranchOpsExt, BranchProtectionExt, BranchRuleExt,
+        CodeScanningExt, CustomPropertyExt, GitHubActionsPermissions, GitHubActorAllowance, GitHubActorAllowances,
+        GitHubBranchRule, GitHubBranchRuleActor, GitHubConnection, GitHubCustomProperty, GitHubPages, GitHubRequiredStatusChecks,
+        GitHubRunnerGroup, InteractionLimitsExt, InteractionLimitsScope, ListExt, PagesExt, RunnerGroupExt, is_not_found,
+    },
     resource,
 };
 use anyhow::Context;
@@ -9,7 +14,11 @@ use autoschematic_core::{
     connector::{GetResourceResponse, Resource, ResourceAddress},
     get_resource_response,
 };
-use std::path::Path;
+use octocrab::Octocrab;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
 
 impl GitHubConnector {
     pub async fn do_get(&self, addr: &Path) -> anyhow::Result<Option<GetResourceResponse>> {
@@ -20,9 +29,35 @@ impl GitHubConnector {
             GitHubResourceAddress::Repository { owner, repo } => {
                 let client = self.client.read().await;
                 match client.repos(&owner, &repo).get().await {
+                    Ok(github_repo) if !is_same_repository(&github_repo, &owner, &repo) => {
+                        // GitHub redirects requests for a renamed or transferred repository to its new location.
+                        // The repository no longer lives at this address, so report it as absent here.
+                        tracing::info!(
+                            "Repository {}/{} now redirects to {}",
+                            owner,
+                            repo,
+                            github_repo.full_name.unwrap_or_default()
+                        );
+                        Ok(None)
+                    }
                     Ok(github_repo) => {
+                        let outputs = repository_outputs(&github_repo);
                         let collaborators = client.list_repo_collaborators(&owner, &repo, Some("direct")).await?;
 
+                        // Repositories owned by users have no custom properties.
+                        let owned_by_user = github_repo.owner.as_ref().is_some_and(|owner| owner.r#type == "User");
+                        let custom_properties = match client.get_custom_property_values(&owner, &repo).await {
+                            Ok(values) => values
+                                .into_iter()
+                                .filter_map(|value| value.value.map(|v| (value.property_name, v)))
+                                .collect(),
+                            Err(e) if owned_by_user => {
+                                tracing::debug!("Failed to get custom property values for {}/{}: {:#}", owner, repo, e);
+                                BTreeMap::new()
+                            }
+                            Err(e) => return Err(e).context("Failed to get the repository's custom property values"),
+                        };
+
                         let repo_resource = resource::GitHubRepository {
                             description: github_repo.description,
                             homepage: github_repo.homepage,
@@ -39,10 +74,29 @@ impl GitHubConnector {
                             default_branch: github_repo.default_branch.unwrap_or_else(|| "main".to_string()),
                             archived: github_repo.archived.unwrap_or(false),
                             disabled: github_repo.disabled.unwrap_or(false),
+                            is_template: github_repo.is_template.unwrap_or(false),
+                            fork_of: github_repo.parent.and_then(|parent| parent.full_name),
+                            fork_default_branch_only: false,
                             collaborators: collaborators,
+                            // Creation-only options aren't reported by the API.
+                            template: None,
+                            auto_init: false,
+                            gitignore_template: None,
+                            license_template: None,
+                            moved_from: None,
+                            default_branch_renamed_from: None,
+                            custom_properties,
                         };
 
-                        get_resource_response!(resource::GitHubResource::Repository(repo_resource))
+                        Ok(Some(GetResourceResponse {
+                            resource_definition: resource::GitHubResource::Repository(repo_resource).to_bytes()?,
+                            outputs: Some(
+                                outputs
+                                    .into_iter()
+                                    .filter_map(|(key, value)| value.map(|value| (key, value)))
+                                    .collect(),
+                            ),
+                        }))
                     }
                     Err(_) => Ok(None), // Repository doesn't exist
                 }
@@ -51,12 +105,7 @@ impl GitHubConnector {
                 match self.client.read().await.get_branch_protection(&owner, &repo, &branch).await {
                     Ok(protection) => {
                         let protection_resource = resource::BranchProtection {
-                            required_status_checks: protection.required_status_checks.map(|checks| {
-                                resource::RequiredStatusChecks {
-                                    strict: checks.strict,
-                                    contexts: checks.contexts,
-                                }
-                            }),
+                            required_status_checks: protection.required_status_checks.map(status_checks_resource),
                             enforce_admins: protection.enforce_admins.enabled,
                             required_pull_request_reviews: protection.required_pull_request_reviews.map(|reviews| {
                                 resource::PullRequestReviewEnforcement {
@@ -64,12 +113,21 @@ impl GitHubConnector {
                                     dismiss_stale_reviews: reviews.dismiss_stale_reviews.unwrap_or(false),
                                     require_code_owner_reviews: reviews.require_code_owner_reviews.unwrap_or(false),
                                     require_last_push_approval: reviews.require_last_push_approval.unwrap_or(false),
+                                    // An empty set of dismissal restrictions or bypass allowances is the same as having none.
+                                    dismissal_restrictions: reviews
+                                        .dismissal_restrictions
+                                        .map(actor_allowances_resource)
+                                        .filter(|allowances| !allowances.is_empty()),
+                                    bypass_pull_request_allowances: reviews
+                                        .bypass_pull_request_allowances
+                                        .map(actor_allowances_resource)
+                                        .filter(|allowances| !allowances.is_empty()),
                                 }
                             }),
                             restrictions: protection.restrictions.map(|restrictions| resource::BranchRestrictions {
                                 users: restrictions.users.into_iter().map(|u| u.login).collect(),
-                                teams: restrictions.teams.into_iter().map(|t| t.name).collect(),
-                                apps: restrictions.apps.into_iter().map(|a| a.name).collect(),
+                                teams: restrictions.teams.into_iter().map(|t| t.slug).collect(),
+                                apps: restrictions.apps.into_iter().map(|a| a.slug).collect(),
                             }),
                             required_linear_history: protection.required_linear_history.map(|s| s.enabled).unwrap_or(false),
                             allow_force_pushes: protection.allow_force_pushes.map(|s| s.enabled).unwrap_or(false),
@@ -81,6 +139,7 @@ impl GitHubConnector {
                                 .unwrap_or(false),
                             lock_branch: protection.lock_branch.map(|s| s.enabled).unwrap_or(false),
                             allow_fork_syncing: protection.allow_fork_syncing.map(|s| s.enabled).unwrap_or(true),
+                            required_signatures: protection.required_signatures.map(|s| s.enabled).unwrap_or(false),
                         };
 
                         get_resource_response!(resource::GitHubResource::BranchProtection(protection_resource))
@@ -91,6 +150,516 @@ impl GitHubConnector {
                     }
                 }
             }
+            GitHubResourceAddress::Branch { owner, repo, branch } => {
+                match self.client.read().await.get_branch(&owner, &repo, &branch).await {
+                    Ok(github_branch) => {
+                        // The source can't be read back, so report where the branch points now.
+                        let outputs = HashMap::from([(String::from("sha"), github_branch.commit.sha.clone())]);
+                        let branch_resource = resource::Branch {
+                            source: github_branch.commit.sha,
+                        };
+
+                        Ok(Some(GetResourceResponse {
+                            resource_definition: resource::GitHubResource::Branch(branch_resource).to_bytes()?,
+                            outputs: Some(outputs),
+                        }))
+                    }
+                    Err(e) if is_not_found(&e) => Ok(None),
+                    Err(e) => Err(e).with_context(|| format!("Failed to get branch {} of {}/{}", branch, owner, repo)),
+                }
+            }
+            GitHubResourceAddress::CustomProperties { org } => match self.client.read().await.get_custom_properties(&org).await {
+                Ok(properties) if properties.is_empty() => Ok(None),
+                Ok(properties) => {
+                    let properties = properties
+                        .into_iter()
+                        .map(|property| Ok((property.property_name.clone(), custom_property_resource(property)?)))
+                        .collect::<anyhow::Result<_>>()?;
+
+                    get_resource_response!(resource::GitHubResource::CustomProperties(resource::CustomProperties {
+                        properties
+                    }))
+                }
+                Err(e) if is_not_found(&e) => Ok(None),
+                Err(e) => Err(e).with_context(|| format!("Failed to get the custom properties of organization {}", org)),
+            },
+            GitHubResourceAddress::ActionsPermissions { owner, repo } => {
+                let client = self.client.read().await.clone();
+                let scope = ActionsScope::Repository {
+                    owner: &owner,
+                    repo: &repo,
+                };
+
+                let permissions = match client.get_actions_permissions(&scope).await {
+                    Ok(permissions) => permissions,
+                    Err(e) => {
+                        tracing::debug!("{:#?}", e);
+                        return Ok(None);
+                    }
+                };
+
+                let (allowed_actions, selected_actions) = actions_policy(&client, &scope, &permissions).await?;
+
+                let mut permissions_resource = resource::ActionsPermissions {
+                    enabled: permissions.enabled.unwrap_or(false),
+                    allowed_actions,
+                    selected_actions,
+                    workflow_permissions: workflow_permissions(&client, &scope).await?,
+                };
+                permissions_resource.clear_unused();
+
+                get_resource_response!(resource::GitHubResource::ActionsPermissions(permissions_resource))
+            }
+            GitHubResourceAddress::OrgActionsPermissions { org } => {
+                let client = self.client.read().await.clone();
+                let scope = ActionsScope::Organization { org: &org };
+
+                let permissions = match client.get_actions_permissions(&scope).await {
+                    Ok(permissions) => permissions,
+                    Err(e) => {
+                        tracing::debug!("{:#?}", e);
+                        return Ok(None);
+                    }
+                };
+
+                let enabled_repositories = match permissions.enabled_repositories.as_deref() {
+                    Some("all") => resource::EnabledRepositories::All,
+                    Some("selected") => resource::EnabledRepositories::Selected(
+                        client
+                            .list_actions_enabled_repositories(&org)
+                            .await
+                            .context("Failed to list the repositories enabled for Actions")?,
+                    ),
+                    _ => resource::EnabledRepositories::Disabled,
+                };
+
+                let (allowed_actions, selected_actions) = actions_policy(&client, &scope, &permissions).await?;
+
+                let mut permissions_resource = resource::OrgActionsPermissions {
+                    enabled_repositories,
+                    allowed_actions,
+                    selected_actions,
+                    workflow_permissions: workflow_permissions(&client, &scope).await?,
+                };
+                permissions_resource.clear_unused();
+
+                get_resource_response!(resource::GitHubResource::OrgActionsPermissions(permissions_resource))
+            }
+            GitHubResourceAddress::ActionsSettings { owner, repo } => {
+                let client = self.client.read().await.clone();
+                let scope = ActionsScope::Repository {
+                    owner: &owner,
+                    repo: &repo,
+                };
+
+                let retention = match client.get_artifact_retention(&scope).await {
+                    Ok(retention) => retention,
+                    Err(e) => {
+                        tracing::debug!("{:#?}", e);
+                        return Ok(None);
+                    }
+                };
+
+                // The fork pull request approval policy only exists for public repositories.
+                let fork_pr_approval = match client.get_fork_pr_approval(&scope).await {
+                    Ok(approval) => Some(
+                        resource::ForkPrApproval::from_str(&approval.approval_policy)
+                            .with_context(|| format!("Unknown fork pull request approval policy {}", approval.approval_policy))?,
+                    ),
+                    Err(e) => {
+                        tracing::debug!("{:#?}", e);
+                        None
+                    }
+                };
+
+                // The access level only exists for private and internal repositories.
+                let access_level = match client.get_actions_access(&scope).await {
+                    Ok(access) => resource::ActionsAccessLevel::from_str(&access.access_level),
+                    Err(e) => {
+                        tracing::debug!("{:#?}", e);
+                        None
+                    }
+                };
+
+                let claim = client
+                    .get_oidc_subject_claim(&scope)
+                    .await
+                    .context("Failed to get the OIDC subject claim template")?;
+
+                let mut settings_resource = resource::ActionsSettings {
+                    artifact_and_log_retention_days: retention.days,
+                    fork_pr_approval,
+                    access_level,
+                    oidc_subject_claim: resource::OidcSubjectClaim {
+                        use_default: claim.use_default,
+                        include_claim_keys: claim.include_claim_keys.unwrap_or_default(),
+                    },
+                };
+                settings_resource.clear_unused();
+
+                get_resource_response!(resource::GitHubResource::ActionsSettings(settings_resource))
+            }
+            GitHubResourceAddress::Pages { owner, repo } => {
+                let client = self.client.read().await.clone();
+
+                let pages = match client.get_pages(&owner, &repo).await {
+                    Ok(pages) => pages,
+                    Err(e) => {
+                        tracing::debug!("{:#?}", e);
+                        return Ok(None);
+                    }
+                };
+
+                let mut outputs = HashMap::new();
+                if let Some(html_url) = &pages.html_url {
+                    outputs.insert(String::from("html_url"), html_url.clone());
+                }
+                if let Some(status) = &pages.status {
+                    outputs.insert(String::from("status"), status.clone());
+                }
+                if let Some(certificate) = &pages.https_certificate {
+                    outputs.insert(String::from("https_certificate_state"), certificate.state.clone());
+                }
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: resource::GitHubResource::Pages(pages_resource(pages)?).to_bytes()?,
+                    outputs: Some(outputs),
+                }))
+            }
+            GitHubResourceAddress::CodeScanning { owner, repo } => {
+                let client = self.client.read().await.clone();
+
+                // Repositories without code scanning available, such as private ones without Advanced Security, return an error.
+                let setup = match client.get_code_scanning_default_setup(&owner, &repo).await {
+                    Ok(setup) => setup,
+                    Err(e) => {
+                        tracing::debug!("{:#?}", e);
+                        return Ok(None);
+                    }
+                };
+
+                let state = resource::CodeScanningState::from_str(&setup.state)
+                    .with_context(|| format!("Unknown code scanning default setup state {}", setup.state))?;
+
+                let mut outputs = HashMap::new();
+                if let Some(updated_at) = &setup.updated_at {
+                    outputs.insert(String::from("updated_at"), updated_at.clone());
+                }
+
+                let mut setup_resource = resource::CodeScanningDefaultSetup {
+                    state,
+                    query_suite: resource::QuerySuite::from_str(setup.query_suite.as_deref().unwrap_or("default")),
+                    languages: setup.languages,
+                };
+                setup_resource.clear_unused();
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: resource::GitHubResource::CodeScanning(setup_resource).to_bytes()?,
+                    outputs: Some(outputs),
+                }))
+            }
+            GitHubResourceAddress::InteractionLimits { owner, repo } => {
+                let scope = InteractionLimitsScope::Repository {
+                    owner: &owner,
+                    repo: &repo,
+                };
+                self.get_interaction_limits(&scope).await
+            }
+            GitHubResourceAddress::OrgInteractionLimits { org } => {
+                self.get_interaction_limits(&InteractionLimitsScope::Organization { org: &org })
+                    .await
+            }
+            GitHubResourceAddress::RunnerGroup { org, name } => {
+                let client = self.client.read().await.clone();
+
+                let group = match client.get_runner_group(&org, &name).await {
+                    Ok(Some(group)) => group,
+                    Ok(None) => return Ok(None),
+                    Err(e) => {
+                        tracing::debug!("{:#?}", e);
+                        return Ok(None);
+                    }
+                };
+
+                let outputs = HashMap::from([(String::from("id"), group.id.to_string())]);
+                let group_resource = runner_group_resource(&client, &org, group).await?;
+
+                Ok(Some(GetResourceResponse {
+                    resource_definition: resource::GitHubResource::RunnerGroup(group_resource).to_bytes()?,
+                    outputs: Some(outputs),
+                }))
+            }
+            GitHubResourceAddress::BranchRule { owner, repo, pattern } => {
+                match self.client.read().await.get_branch_rule(&owner, &repo, &pattern).await {
+                    Ok(Some(rule)) => {
+                        let outputs = HashMap::from([(String::from("id"), rule.id.clone())]);
+
+                        Ok(Some(GetResourceResponse {
+                            resource_definition: resource::GitHubResource::BranchProtection(branch_rule_resource(rule))
+                                .to_bytes()?,
+                            outputs: Some(outputs),
+                        }))
+                    }
+                    Ok(None) => Ok(None),
+                    Err(e) => Err(e)
+                        .with_context(|| format!("Failed to get the branch rule for {}/{} pattern {}", owner, repo, pattern)),
+                }
+            }
+        }
+    }
+
+    /// Read an interaction limit. GitHub lifts a limit when it expires, so an expired limit reads back as absent.
+    async fn get_interaction_limits(&self, scope: &InteractionLimitsScope<'_>) -> anyhow::Result<Option<GetResourceResponse>> {
+        let limits = match self.client.read().await.get_interaction_limits(scope).await {
+            Ok(limits) => limits,
+            Err(e) => {
+                tracing::debug!("{:#?}", e);
+                return Ok(None);
+            }
+        };
+
+        if !scope.owns(&limits) {
+            return Ok(None);
+        }
+
+        let Some(limit) = limits.limit else {
+            return Ok(None);
+        };
+        let limit =
+            resource::InteractionLimit::from_str(&limit).with_context(|| format!("Unknown interaction limit {}", limit))?;
+
+        let mut outputs = HashMap::new();
+        if let Some(expires_at) = limits.expires_at {
+            outputs.insert(String::from("expires_at"), expires_at);
+        }
+        if let Some(origin) = limits.origin {
+            outputs.insert(String::from("origin"), origin);
         }
+
+        // Only the end time can be read back, and the expiry isn't compared, so report the default.
+        let limits_resource = resource::InteractionLimits {
+            limit,
+            expiry: resource::InteractionLimitExpiry::default(),
+        };
+
+        Ok(Some(GetResourceResponse {
+            resource_definition: resource::GitHubResource::InteractionLimits(limits_resource).to_bytes()?,
+            outputs: Some(outputs),
+        }))
+    }
+}
+
+/// Split the API's required status checks into unpinned contexts and app-pinned checks. The
+/// `checks` array supersedes the deprecated `contexts` array when it's present.
+fn status_checks_resource(checks: GitHubRequiredStatusChecks) -> resource::RequiredStatusChecks {
+    if checks.checks.is_empty() {
+        return resource::RequiredStatusChecks {
+            strict: checks.strict,
+            contexts: checks.contexts,
+            checks: Vec::new(),
+        };
+    }
+
+    let mut contexts = Vec::new();
+    let mut pinned = Vec::new();
+    for check in checks.checks {
+        match check.app_id {
+            Some(app_id) if app_id > 0 => pinned.push(resource::StatusCheck {
+                context: check.context,
+                app_id: Some(app_id),
+                app: None,
+            }),
+            _ => contexts.push(check.context),
+        }
+    }
+
+    resource::RequiredStatusChecks {
+        strict: checks.strict,
+        contexts,
+        checks: pinned,
+    }
+}
+
+fn actor_allowances_resource(allowances: GitHubActorAllowances) -> resource::ActorAllowances {
+    resource::ActorAllowances {
+        users: allowances.users.into_iter().map(|u| u.login).collect(),
+        teams: allowances.teams.into_iter().map(|t| t.slug).collect(),
+        apps: allowances.apps.into_iter().map(|a| a.slug).collect(),
+    }
+}
+
+fn graphql_actor_allowances(allowances: GitHubConnection<GitHubActorAllowance>) -> resource::ActorAllowances {
+    let mut res = resource::ActorAllowances::default();
+    for allowance in allowances.nodes {
+        match allowance.actor {
+            Some(GitHubBranchRuleActor::User { login }) => res.users.push(login),
+            Some(GitHubBranchRuleActor::Team { slug }) => res.teams.push(slug),
+            Some(GitHubBranchRuleActor::App { slug }) => res.apps.push(slug),
+            _ => {}
+        }
+    }
+    res
+}
+
+/// Convert a pattern rule from the GraphQL API into the same shape as single-branch protection.
+pub fn branch_rule_resource(rule: GitHubBranchRule) -> resource::BranchProtection {
+    let push_allowances = graphql_actor_allowances(rule.push_allowances);
+    let dismissal_allowances = graphql_actor_allowances(rule.review_dismissal_allowances);
+    let bypass_allowances = graphql_actor_allowances(rule.bypass_pull_request_allowances);
+
+    resource::BranchProtection {
+        required_status_checks: rule.requires_status_checks.then(|| resource::RequiredStatusChecks {
+            strict: rule.requires_strict_status_checks,
+            contexts: rule.required_status_check_contexts,
+            checks: Vec::new(),
+        }),
+        enforce_admins: rule.is_admin_enforced,
+        required_pull_request_reviews: rule.requires_approving_reviews.then(|| resource::PullRequestReviewEnforcement {
+            required_approving_review_count: rule.required_approving_review_count.unwrap_or(1),
+            dismiss_stale_reviews: rule.dismisses_stale_reviews,
+            require_code_owner_reviews: rule.requires_code_owner_reviews,
+            require_last_push_approval: rule.require_last_push_approval,
+            dismissal_restrictions: rule.restricts_review_dismissals.then_some(dismissal_allowances),
+            bypass_pull_request_allowances: (!bypass_allowances.is_empty()).then_some(bypass_allowances),
+        }),
+        restrictions: rule.restricts_pushes.then(|| resource::BranchRestrictions {
+            users: push_allowances.users,
+            teams: push_allowances.teams,
+            apps: push_allowances.apps,
+        }),
+        required_linear_history: rule.requires_linear_history,
+        allow_force_pushes: rule.allows_force_pushes,
+        allow_deletions: rule.allows_deletions,
+        block_creations: rule.blocks_creations,
+        required_conversation_resolution: rule.requires_conversation_resolution,
+        lock_branch: rule.lock_branch,
+        allow_fork_syncing: rule.lock_allows_fetch_and_merge,
+        required_signatures: rule.requires_commit_signatures,
+    }
+}
+
+/// Read the allowed actions, and the selected actions if only those are allowed.
+async fn actions_policy(
+    client: &Octocrab,
+    scope: &ActionsScope<'_>,
+    permissions: &GitHubActionsPermissions,
+) -> anyhow::Result<(resource::AllowedActions, Option<resource::SelectedActions>)> {
+    let allowed_actions = resource::AllowedActions::from_str(permissions.allowed_actions.as_deref().unwrap_or("all"));
+
+    // The selected actions can only be read while they're in effect.
+    let selected_actions = match allowed_actions {
+        resource::AllowedActions::Selected => {
+            let selected = client
+                .get_selected_actions(scope)
+                .await
+                .context("Failed to get the selected actions")?;
+
+            Some(resource::SelectedActions {
+                github_owned_allowed: selected.github_owned_allowed,
+                verified_allowed: selected.verified_allowed,
+                patterns_allowed: selected.patterns_allowed,
+            })
+        }
+        _ => None,
+    };
+
+    Ok((allowed_actions, selected_actions))
+}
+
+async fn workflow_permissions(client: &Octocrab, scope: &ActionsScope<'_>) -> anyhow::Result<resource::WorkflowPermissions> {
+    let workflow = client
+        .get_workflow_permissions(scope)
+        .await
+        .context("Failed to get the default workflow permissions")?;
+
+    Ok(resource::WorkflowPermissions {
+        default_workflow_permissions: resource::WorkflowTokenPermissions::from_str(&workflow.default_workflow_permissions),
+        can_approve_pull_request_reviews: workflow.can_approve_pull_request_reviews,
+    })
+}
+
+pub fn pages_resource(pages: GitHubPages) -> anyhow::Result<resource::Pages> {
+    let source = match (pages.build_type.as_deref(), pages.source) {
+        (Some("workflow"), _) => resource::PagesSource::Workflow,
+        (_, Some(source)) => resource::PagesSource::Branch {
+            branch: source.branch,
+            path: source.path,
+        },
+        (build_type, None) => anyhow::bail!("GitHub Pages site has build type {:?} but no source", build_type),
+    };
+
+    Ok(resource::Pages {
+        source,
+        custom_domain: pages.cname.filter(|cname| !cname.is_empty()),
+        https_enforced: pages.https_enforced.unwrap_or(false),
+        visibility: pages.public.map(|public| match public {
+            true => resource::PagesVisibility::Public,
+            false => resource::PagesVisibility::Private,
+        }),
+    })
+}
+
+/// Convert a runner group from the API, listing its repositories if only selected ones may use it.
+pub async fn runner_group_resource(
+    client: &Octocrab,
+    org: &str,
+    group: GitHubRunnerGroup,
+) -> anyhow::Result<resource::RunnerGroup> {
+    let visibility = match group.visibility.as_str() {
+        "all" => resource::RunnerGroupVisibility::All,
+        "selected" => resource::RunnerGroupVisibility::Selected(
+            client
+                .list_runner_group_repositories(org, group.id)
+                .await
+                .with_context(|| format!("Failed to list the repositories for runner group {}", group.name))?,
+        ),
+        "private" => resource::RunnerGroupVisibility::Private,
+        visibility => anyhow::bail!("Runner group {} has unknown visibility {}", group.name, visibility),
+    };
+
+    let mut group_resource = resource::RunnerGroup {
+        visibility,
+        allows_public_repositories: group.allows_public_repositories,
+        restricted_to_workflows: group.restricted_to_workflows.then_some(group.selected_workflows),
+    };
+    group_resource.clear_unused();
+
+    Ok(group_resource)
+}
+
+fn custom_property_resource(property: GitHubCustomProperty) -> anyhow::Result<resource::CustomProperty> {
+    let value_type = resource::CustomPropertyType::from_str(&property.value_type).with_context(|| {
+        format!(
+            "Custom property {} has unknown value type {}",
+            property.property_name, property.value_type
+        )
+    })?;
+
+    Ok(resource::CustomProperty {
+        value_type,
+        required: property.required,
+        default_value: property.default_value,
+        description: property.description,
+        allowed_values: property.allowed_values.unwrap_or_default(),
+    })
+}
+
+/// The outputs for a repository, so that other resources and templates can refer to it.
+pub fn repository_outputs(github_repo: &octocrab::models::Repository) -> HashMap<String, Option<String>> {
+    HashMap::from([
+        (String::from("id"), Some(github_repo.id.to_string())),
+        (String::from("node_id"), github_repo.node_id.clone()),
+        (String::from("html_url"), github_repo.html_url.as_ref().map(|url| url.to_string())),
+        (String::from("clone_url"), github_repo.clone_url.as_ref().map(|url| url.to_string())),
+        (String::from("ssh_url"), github_repo.ssh_url.clone()),
+        (String::from("default_branch"), github_repo.default_branch.clone()),
+    ])
+}
+
+/// Whether `github_repo` is the repository at `owner/repo`, rather than one that was redirected to.
+pub fn is_same_repository(github_repo: &octocrab::models::Repository, owner: &str, repo: &str) -> bool {
+    match &github_repo.full_name {
+        Some(full_name) => full_name.eq_ignore_ascii_case(&format!("{}/{}", owner, repo)),
+        None => true,
     }
 }
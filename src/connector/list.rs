@@ -4,9 +4,18 @@ use futures_util::TryStreamExt;
 use octocrab::{Octocrab, Page, models::Repository};
 use tokio::pin;
 
-use crate::{GitHubConnector, addr::GitHubResourceAddress, github_ext::ListExt};
+use crate::{
+    GitHubConnector,
+    addr::GitHubResourceAddress,
+    github_ext::{BranchRuleExt, CustomPropertyExt, InteractionLimitsExt, InteractionLimitsScope, ListExt, RunnerGroupExt},
+};
 use std::path::{Path, PathBuf};
 
+/// Whether a branch rule pattern can match more than one branch, as opposed to naming a single branch.
+pub fn is_wildcard_pattern(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
 pub async fn list_repo_stream(owner: String, client: &Octocrab, page: Page<Repository>) -> anyhow::Result<Vec<PathBuf>> {
     let mut results = Vec::new();
     let repo_stream = page.into_stream(&client);
@@ -19,6 +28,65 @@ pub async fn list_repo_stream(owner: String, client: &Octocrab, page: Page<Repos
         };
         results.push(addr.to_path_buf());
 
+        // Every repository has Actions permissions, even if they're only the defaults.
+        let addr = GitHubResourceAddress::ActionsPermissions {
+            owner: owner.clone(),
+            repo: repo.name.clone(),
+        };
+        results.push(addr.to_path_buf());
+
+        let addr = GitHubResourceAddress::ActionsSettings {
+            owner: owner.clone(),
+            repo: repo.name.clone(),
+        };
+        results.push(addr.to_path_buf());
+
+        let addr = GitHubResourceAddress::CodeScanning {
+            owner: owner.clone(),
+            repo: repo.name.clone(),
+        };
+        results.push(addr.to_path_buf());
+
+        // Interaction limits only apply to public repositories, and only exist while in effect. A limit
+        // inherited from the organization is listed at the organization instead.
+        if repo.private != Some(true) {
+            let scope = InteractionLimitsScope::Repository {
+                owner: &owner,
+                repo: &repo.name,
+            };
+            if client
+                .get_interaction_limits(&scope)
+                .await
+                .is_ok_and(|limits| limits.limit.is_some() && scope.owns(&limits))
+            {
+                let addr = GitHubResourceAddress::InteractionLimits {
+                    owner: owner.clone(),
+                    repo: repo.name.clone(),
+                };
+                results.push(addr.to_path_buf());
+            }
+        }
+
+        if repo.has_pages.unwrap_or(false) {
+            let addr = GitHubResourceAddress::Pages {
+                owner: owner.clone(),
+                repo: repo.name.clone(),
+            };
+            results.push(addr.to_path_buf());
+        }
+
+        // Single-branch protection is also a rule whose pattern is the branch name. Those are listed as
+        // branches/{branch}/protection.ron, so only wildcard patterns and rules naming a branch that
+        // doesn't exist (yet) get a branch_rules file.
+        let rules = match client.list_branch_rules(&owner, &repo.name).await {
+            Ok(rules) => Some(rules),
+            Err(e) => {
+                tracing::debug!("Failed to list branch rules for {}/{}: {:#}", owner, repo.name, e);
+                None
+            }
+        };
+
+        let mut branch_names = Vec::new();
         match client.list_repo_branches(&owner, &repo.name).await {
             Ok(branch_page) => {
                 let branch_stream = branch_page.into_stream(&client);
@@ -26,19 +94,39 @@ pub async fn list_repo_stream(owner: String, client: &Octocrab, page: Page<Repos
 
                 while let Some(branch) = branch_stream.try_next().await? {
                     tracing::info!("...{}...", branch.name);
-                    if branch.protected {
+                    // A branch that's only protected through a wildcard rule has no protection of its own.
+                    let has_own_protection = match &rules {
+                        Some(rules) => rules.iter().any(|rule| rule.pattern == branch.name),
+                        None => branch.protected,
+                    };
+
+                    if has_own_protection {
                         let addr = GitHubResourceAddress::BranchProtection {
                             owner: owner.clone(),
                             repo: repo.name.clone(),
-                            branch: branch.name,
+                            branch: branch.name.clone(),
                         };
                         results.push(addr.to_path_buf());
                     }
+                    branch_names.push(branch.name);
                 }
             }
             Err(_) => {}
         }
 
+        for rule in rules.unwrap_or_default() {
+            if !is_wildcard_pattern(&rule.pattern) && branch_names.contains(&rule.pattern) {
+                continue;
+            }
+
+            let addr = GitHubResourceAddress::BranchRule {
+                owner: owner.clone(),
+                repo: repo.name.clone(),
+                pattern: rule.pattern,
+            };
+            results.push(addr.to_path_buf());
+        }
+
         // match client.list_repo_collaborators(&owner, &repo.name).await {
         //     Ok(collaborator_page) => {
         //         let collaborator_stream = collaborator_page.into_stream(&client);
@@ -102,6 +190,43 @@ impl GitHubConnector {
 
             let client = self.client.read().await.clone();
 
+            results.push(GitHubResourceAddress::OrgActionsPermissions { org: org.clone() }.to_path_buf());
+
+            match client.get_custom_properties(&org).await {
+                Ok(properties) if !properties.is_empty() => {
+                    results.push(GitHubResourceAddress::CustomProperties { org: org.clone() }.to_path_buf());
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::debug!("Failed to list custom properties for {}: {:#}", org, e);
+                }
+            }
+
+            let scope = InteractionLimitsScope::Organization { org: &org };
+            if client
+                .get_interaction_limits(&scope)
+                .await
+                .is_ok_and(|limits| limits.limit.is_some())
+            {
+                results.push(GitHubResourceAddress::OrgInteractionLimits { org: org.clone() }.to_path_buf());
+            }
+
+            // Groups inherited from the enterprise can't be changed from the organization.
+            match client.list_runner_groups(&org).await {
+                Ok(groups) => {
+                    for group in groups.into_iter().filter(|group| !group.inherited) {
+                        let addr = GitHubResourceAddress::RunnerGroup {
+                            org: org.clone(),
+                            name: group.name,
+                        };
+                        results.push(addr.to_path_buf());
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!("Failed to list runner groups for {}: {:#}", org, e);
+                }
+            }
+
             match client.orgs(&org).list_repos().send().await {
                 Ok(repos_page) => {
                     results.append(&mut list_repo_stream(org, &client, repos_page).await?);
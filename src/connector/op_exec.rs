@@ -1,23 +1,438 @@
 use crate::{
     GitHubConnector,
     addr::GitHubResourceAddress,
+    connector::get::{branch_rule_resource, is_same_repository, pages_resource, repository_outputs, runner_group_resource},
     github_ext::{
-        AddCollaboratorRequest, AddTeamCollaboratorRequest, BranchProtectionOpsExt, CollaboratorOpsExt,
-        CreateBranchProtectionRequest, CreateRepositoryRequest, RepositoryOpsExt, UpdateRepositoryRequest,
+        ActionsPermissionsExt, ActionsScope, ActionsSettingsExt, AddCollaboratorRequest, AddTeamCollaboratorRequest,
+        BranchOpsExt, BranchProtectionOpsExt, BranchRuleExt, CodeScanningExt, CollaboratorOpsExt, CreateBranchProtectionRequest,
+        CreatePagesRequest, CreateRepositoryRequest, CustomPropertyExt, ForkRepositoryRequest, GenerateRepositoryRequest,
+        GitHubActionsAccess, GitHubActionsPermissions, GitHubActorAllowances, GitHubApp, GitHubArtifactRetention,
+        GitHubBranchRestrictions, GitHubCustomProperty, GitHubCustomPropertyValue, GitHubForkPrApproval, GitHubOidcSubjectClaim,
+        GitHubPagesSource, GitHubRequiredStatusChecks, GitHubSelectedActions, GitHubStatusCheck, GitHubTeam, GitHubUser,
+        GitHubWorkflowPermissions, GitHubWorkflowRun, InteractionLimitsExt, InteractionLimitsScope, ListExt, PagesExt,
+        RenameBranchRequest, ReplaceTopicsRequest, RepositoryOpsExt, RunnerGroupExt, RunnerGroupRequest,
+        SetCustomPropertyValuesRequest, SetInteractionLimitsRequest, SetSelectedRepositoriesRequest, TransferRepositoryRequest,
+        UpdateCodeScanningDefaultSetupRequest, UpdatePagesRequest, UpdateRepositoryRequest, UpsertCustomPropertiesRequest,
+        is_not_found, is_pending_domain_error,
     },
     op::GitHubConnectorOp,
+    resource::{
+        ActorAllowances, Branch, BranchProtection, BranchRestrictions, CodeScanningState, CollaboratorPrincipal, CustomProperty,
+        EnabledRepositories, GitHubRepository, Pages, PagesSource, PagesVisibility, RequiredStatusChecks, RunnerGroup,
+        RunnerGroupVisibility, SelectedActions, WorkflowPermissions,
+    },
 };
-use anyhow::bail;
+use anyhow::{Context, bail};
 use autoschematic_core::{
     connector::{ConnectorOp, OpExecResponse, ResourceAddress},
     error_util::invalid_op,
+    util::RON,
 };
-use std::path::Path;
+use octocrab::Octocrab;
+use std::{collections::HashMap, path::Path, time::Duration};
+
+/// How long to wait for GitHub to finish creating a fork or transferring a repository.
+const FORK_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Look up the ids of repositories in `org` by name, for the endpoints that take a list of selected repositories.
+async fn repository_ids(client: &Octocrab, org: &str, repos: &[String]) -> anyhow::Result<Vec<u64>> {
+    let mut ids = Vec::new();
+    for repo in repos {
+        match client.repos(org, repo).get().await {
+            Ok(github_repo) => ids.push(github_repo.id.into_inner()),
+            Err(e) => bail!("Failed to look up repository {}/{}: {}", org, repo, e),
+        }
+    }
+    Ok(ids)
+}
+
+fn pages_build_type(source: &PagesSource) -> String {
+    match source {
+        PagesSource::Branch { .. } => String::from("legacy"),
+        PagesSource::Workflow => String::from("workflow"),
+    }
+}
+
+fn pages_source_request(source: &PagesSource) -> Option<GitHubPagesSource> {
+    match source {
+        PagesSource::Branch { branch, path } => Some(GitHubPagesSource {
+            branch: branch.clone(),
+            path: path.clone(),
+        }),
+        PagesSource::Workflow => None,
+    }
+}
+
+/// Set a Pages site's domain, HTTPS enforcement, source and visibility. GitHub holds back a custom domain
+/// until its DNS is verified, and HTTPS enforcement until its certificate is issued. Those come back as a
+/// warning rather than an error, since they clear up on their own and the next plan tries again.
+async fn apply_pages_update(client: &Octocrab, owner: &str, repo: &str, pages: &Pages) -> anyhow::Result<Option<String>> {
+    let mut request = UpdatePagesRequest {
+        cname: Some(pages.custom_domain.clone()),
+        https_enforced: Some(pages.https_enforced),
+        build_type: pages_build_type(&pages.source),
+        source: pages_source_request(&pages.source),
+        public: pages
+            .visibility
+            .as_ref()
+            .map(|visibility| *visibility == PagesVisibility::Public),
+    };
+
+    let Some(domain) = &pages.custom_domain else {
+        if let Err(e) = client.update_pages(owner, repo, &request).await {
+            bail!("Failed to update GitHub Pages for {}/{}: {:#?}", owner, repo, e);
+        }
+        return Ok(None);
+    };
+
+    match client.update_pages(owner, repo, &request).await {
+        Ok(()) => return Ok(None),
+        Err(e) if is_pending_domain_error(&e) => tracing::warn!("GitHub held back the Pages domain {}: {:#?}", domain, e),
+        Err(e) => bail!("Failed to update GitHub Pages for {}/{}: {:#?}", owner, repo, e),
+    }
+
+    // The certificate usually lags the DNS, so try again without HTTPS enforcement.
+    if pages.https_enforced {
+        request.https_enforced = None;
+        match client.update_pages(owner, repo, &request).await {
+            Ok(()) => {
+                return Ok(Some(format!(
+                    "WARNING: HTTPS isn't enforced for {} yet because its certificate hasn't been issued. \
+                     The next plan will try again.",
+                    domain
+                )));
+            }
+            Err(e) if is_pending_domain_error(&e) => tracing::warn!("GitHub held back the Pages domain {}: {:#?}", domain, e),
+            Err(e) => bail!("Failed to update GitHub Pages for {}/{}: {:#?}", owner, repo, e),
+        }
+    }
+
+    // Still apply the rest of the settings, leaving the domain and HTTPS enforcement as they are.
+    request.cname = None;
+    request.https_enforced = None;
+    if let Err(e) = client.update_pages(owner, repo, &request).await {
+        bail!("Failed to update GitHub Pages for {}/{}: {:#?}", owner, repo, e);
+    }
+
+    Ok(Some(format!(
+        "WARNING: GitHub Pages for {}/{} was updated, except for the custom domain {} because its DNS isn't verified yet. \
+         The next plan will try again.",
+        owner, repo, domain
+    )))
+}
+
+fn runner_group_request(name: &str, group_config: &RunnerGroup) -> RunnerGroupRequest {
+    RunnerGroupRequest {
+        name: name.to_string(),
+        visibility: group_config.visibility.to_string(),
+        selected_repository_ids: None,
+        allows_public_repositories: group_config.allows_public_repositories,
+        restricted_to_workflows: group_config.restricted_to_workflows.is_some(),
+        selected_workflows: group_config.restricted_to_workflows.clone().unwrap_or_default(),
+    }
+}
+
+/// A successful response for an op whose end state was already in place.
+fn no_op(message: String) -> anyhow::Result<OpExecResponse> {
+    Ok(OpExecResponse {
+        outputs: None,
+        friendly_message: Some(format!("{}; nothing to do", message)),
+    })
+}
+
+/// Build the API's required status checks. Once any check is pinned to an app, every check goes
+/// in the `checks` array, with the unpinned ones explicitly allowing any app.
+fn status_checks_request(checks: &RequiredStatusChecks) -> GitHubRequiredStatusChecks {
+    if checks.checks.is_empty() {
+        return GitHubRequiredStatusChecks {
+            strict: checks.strict,
+            contexts: checks.contexts.clone(),
+            checks: Vec::new(),
+        };
+    }
+
+    let unpinned = checks.contexts.iter().map(|context| GitHubStatusCheck {
+        context: context.clone(),
+        app_id: Some(-1),
+    });
+    let pinned = checks.checks.iter().map(|check| GitHubStatusCheck {
+        context: check.context.clone(),
+        app_id: check.app_id,
+    });
+
+    GitHubRequiredStatusChecks {
+        strict: checks.strict,
+        contexts: Vec::new(),
+        checks: unpinned.chain(pinned).collect(),
+    }
+}
+
+/// Required signatures have their own endpoint, so they're set after the protection itself.
+async fn set_required_signatures(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    branch: &str,
+    enabled: bool,
+) -> octocrab::Result<()> {
+    if enabled {
+        client.enable_required_signatures(owner, repo, branch).await?;
+        return Ok(());
+    }
+
+    match client.disable_required_signatures(owner, repo, branch).await {
+        Err(e) if is_not_found(&e) => Ok(()),
+        result => result,
+    }
+}
+
+/// Build the API's users, teams and apps from their logins and slugs. Only the login and slug are sent.
+fn actor_allowances_request(allowances: &ActorAllowances) -> GitHubActorAllowances {
+    GitHubActorAllowances {
+        users: allowances.users.iter().map(|u| GitHubUser { login: u.clone() }).collect(),
+        teams: allowances
+            .teams
+            .iter()
+            .map(|t| GitHubTeam {
+                name: t.clone(),
+                slug: t.clone(),
+            })
+            .collect(),
+        apps: allowances
+            .apps
+            .iter()
+            .map(|a| GitHubApp {
+                name: a.clone(),
+                slug: a.clone(),
+            })
+            .collect(),
+    }
+}
+
+/// Like `actor_allowances_request`, but when there are none and `clear` is set, sends an empty set, which
+/// is how the API lifts a review restriction. Leaving the field out keeps the existing one, and is the
+/// only option for personal repositories.
+fn review_allowances_request(allowances: Option<&ActorAllowances>, clear: bool) -> Option<GitHubActorAllowances> {
+    match allowances {
+        Some(allowances) => Some(actor_allowances_request(allowances)),
+        None if clear => Some(GitHubActorAllowances::default()),
+        None => None,
+    }
+}
+
+fn branch_restrictions_request(restrictions: &BranchRestrictions) -> GitHubBranchRestrictions {
+    actor_allowances_request(&ActorAllowances {
+        users: restrictions.users.clone(),
+        teams: restrictions.teams.clone(),
+        apps: restrictions.apps.clone(),
+    })
+}
+
+/// Look up the node IDs of users, teams (in the `org` organization) and apps, for GraphQL mutations.
+async fn resolve_actor_ids(client: &Octocrab, org: &str, allowances: &ActorAllowances) -> anyhow::Result<Vec<String>> {
+    let mut ids = Vec::new();
+
+    for user in &allowances.users {
+        match client.get_user_node_id(user).await {
+            Ok(node) => ids.push(node.node_id),
+            Err(e) => bail!("Failed to look up user {}: {}", user, e),
+        }
+    }
+    for team in &allowances.teams {
+        match client.get_team_node_id(org, team).await {
+            Ok(node) => ids.push(node.node_id),
+            Err(e) => bail!("Failed to look up team {}/{}: {}", org, team, e),
+        }
+    }
+    for app in &allowances.apps {
+        match client.get_app_node_id(app).await {
+            Ok(node) => ids.push(node.node_id),
+            Err(e) => bail!("Failed to look up app {}: {}", app, e),
+        }
+    }
+
+    Ok(ids)
+}
+
+fn custom_property_request(name: &str, property: &CustomProperty) -> GitHubCustomProperty {
+    GitHubCustomProperty {
+        property_name: name.to_string(),
+        value_type: property.value_type.to_string(),
+        required: property.required,
+        default_value: property.default_value.clone(),
+        description: property.description.clone(),
+        allowed_values: if property.allowed_values.is_empty() {
+            None
+        } else {
+            Some(property.allowed_values.clone())
+        },
+    }
+}
+
+/// Set the selected actions, which are only present while in effect, and the default workflow permissions.
+/// These follow the permissions themselves, since selected actions can't be set until they're in effect.
+async fn apply_actions_policy(
+    client: &Octocrab,
+    scope: &ActionsScope<'_>,
+    selected_actions: Option<&SelectedActions>,
+    workflow_permissions: &WorkflowPermissions,
+) -> anyhow::Result<()> {
+    if let Some(selected_actions) = selected_actions.cloned() {
+        let request = GitHubSelectedActions {
+            github_owned_allowed: selected_actions.github_owned_allowed,
+            verified_allowed: selected_actions.verified_allowed,
+            patterns_allowed: selected_actions.patterns_allowed,
+        };
+
+        if let Err(e) = client.set_selected_actions(scope, &request).await {
+            bail!("Failed to set the selected actions: {:#?}", e);
+        }
+    }
+
+    let request = GitHubWorkflowPermissions {
+        default_workflow_permissions: workflow_permissions.default_workflow_permissions.to_string(),
+        can_approve_pull_request_reviews: workflow_permissions.can_approve_pull_request_reviews,
+    };
+
+    if let Err(e) = client.set_workflow_permissions(scope, &request).await {
+        bail!("Failed to set the default workflow permissions: {:#?}", e);
+    }
+
+    Ok(())
+}
+
+/// The settings that the fork and generate endpoints can't take, applied with a follow-up update.
+fn post_creation_settings(repo_config: &GitHubRepository) -> UpdateRepositoryRequest {
+    UpdateRepositoryRequest {
+        description: repo_config.description.clone(),
+        homepage: repo_config.homepage.clone(),
+        has_issues: Some(repo_config.has_issues),
+        has_projects: Some(repo_config.has_projects),
+        has_wiki: Some(repo_config.has_wiki),
+        allow_squash_merge: Some(repo_config.allow_squash_merge),
+        allow_merge_commit: Some(repo_config.allow_merge_commit),
+        allow_rebase_merge: Some(repo_config.allow_rebase_merge),
+        allow_auto_merge: Some(repo_config.allow_auto_merge),
+        delete_branch_on_merge: Some(repo_config.delete_branch_on_merge),
+        is_template: Some(repo_config.is_template),
+        ..Default::default()
+    }
+}
+
+/// Poll until the repository exists, or bail once `timeout` has elapsed.
+async fn wait_for_repository(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    timeout: Duration,
+) -> anyhow::Result<octocrab::models::Repository> {
+    let start = tokio::time::Instant::now();
+    let mut delay = Duration::from_secs(1);
+
+    loop {
+        match client.repos(owner, repo).get().await {
+            Ok(github_repo) => return Ok(github_repo),
+            Err(e) => {
+                if start.elapsed() >= timeout {
+                    bail!(
+                        "Timed out after {}s waiting for repository {}/{} to be created: {}",
+                        timeout.as_secs(),
+                        owner,
+                        repo,
+                        e
+                    );
+                }
+                tracing::debug!("Waiting for repository {}/{} to exist...", owner, repo);
+            }
+        }
+
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(Duration::from_secs(10));
+    }
+}
+
+/// Poll a workflow run until it completes, returning None if it's still going once `timeout` has elapsed.
+async fn wait_for_workflow_run(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    run_id: u64,
+    timeout: Duration,
+) -> anyhow::Result<Option<GitHubWorkflowRun>> {
+    let start = tokio::time::Instant::now();
+    let mut delay = Duration::from_secs(1);
+
+    loop {
+        let run = match client.get_workflow_run(owner, repo, run_id).await {
+            Ok(run) => run,
+            Err(e) => bail!("Failed to get workflow run {} in {}/{}: {:#?}", run_id, owner, repo, e),
+        };
+
+        if run.status.as_deref() == Some("completed") {
+            return Ok(Some(run));
+        }
+        if start.elapsed() >= timeout {
+            return Ok(None);
+        }
+        tracing::debug!("Waiting for workflow run {} in {}/{} to complete...", run_id, owner, repo);
+
+        // The last wait only runs out the timeout, so the final check isn't late.
+        tokio::time::sleep(delay.min(timeout.saturating_sub(start.elapsed()))).await;
+        delay = (delay * 2).min(Duration::from_secs(10));
+    }
+}
 
 impl GitHubConnector {
+    /// If the repository already exists with the desired settings, report success instead of creating it.
+    /// Bails if it exists with different settings, since creating it again would fail anyway.
+    async fn adopt_existing_repository(
+        &self,
+        addr: &GitHubResourceAddress,
+        desired: &GitHubRepository,
+    ) -> anyhow::Result<Option<OpExecResponse>> {
+        let Some(existing) = self.do_get(&addr.to_path_buf()).await? else {
+            return Ok(None);
+        };
+
+        let outputs = existing
+            .outputs
+            .map(|outputs| outputs.into_iter().map(|(key, value)| (key, Some(value))).collect());
+        let mut existing: GitHubRepository = RON.from_str(str::from_utf8(&existing.resource_definition)?)?;
+        let mut desired = desired.clone();
+
+        // Topics, collaborators and custom properties are planned as separate ops, and creation-only options can't be read back.
+        for repo_config in [&mut existing, &mut desired] {
+            repo_config.clear_creation_options();
+            repo_config.topics.clear();
+            repo_config.collaborators.clear();
+            repo_config.custom_properties.clear();
+        }
+
+        if existing != desired {
+            bail!(
+                "{} already exists with different settings. Re-run the plan to update it instead.",
+                addr.to_path_buf().display()
+            );
+        }
+
+        Ok(Some(OpExecResponse {
+            outputs,
+            friendly_message: Some(format!(
+                "{} already exists with the desired settings; nothing to do",
+                addr.to_path_buf().display()
+            )),
+        }))
+    }
+
     pub async fn do_op_exec(&self, addr: &Path, op: &str) -> anyhow::Result<OpExecResponse> {
         let addr = GitHubResourceAddress::from_path(addr)?;
-        let op = GitHubConnectorOp::from_str(op)?;
+        let op = match GitHubConnectorOp::from_str(op)? {
+            GitHubConnectorOp::Guarded(planned, op) => {
+                self.check_drift(&addr, &planned).await?;
+                *op
+            }
+            op => op,
+        };
 
         match &addr {
             GitHubResourceAddress::Config => Err(invalid_op(&addr, &op)),
@@ -26,6 +441,112 @@ impl GitHubConnector {
 
                 match op {
                     GitHubConnectorOp::CreateRepository(repo_config) => {
+                        // A retried create may find that GitHub already processed the first attempt.
+                        if let Some(response) = self.adopt_existing_repository(&addr, &repo_config).await? {
+                            return Ok(response);
+                        }
+
+                        if let Some(fork_of) = &repo_config.fork_of {
+                            let Some((upstream_owner, upstream_repo)) = fork_of.split_once('/') else {
+                                bail!(
+                                    "Invalid fork_of repository {:?} for {}/{}: expected \"owner/repo\"",
+                                    fork_of,
+                                    owner,
+                                    repo
+                                );
+                            };
+
+                            let organization = if self.config.read().await.orgs.contains(owner) {
+                                Some(owner.clone())
+                            } else {
+                                None
+                            };
+
+                            let fork_request = ForkRepositoryRequest {
+                                organization,
+                                name: repo.clone(),
+                                default_branch_only: repo_config.fork_default_branch_only,
+                            };
+
+                            if let Err(e) = client.fork_repository(upstream_owner, upstream_repo, &fork_request).await {
+                                bail!("Failed to fork {} into {}/{}: {}", fork_of, owner, repo, e);
+                            }
+
+                            // Forks are created asynchronously; the fork request returns before the repository exists.
+                            wait_for_repository(&client, owner, repo, FORK_TIMEOUT).await?;
+
+                            let update_request = post_creation_settings(&repo_config);
+
+                            return match client.update_repository(owner, repo, &update_request).await {
+                                Ok(github_repo) => Ok(OpExecResponse {
+                                    outputs: Some(repository_outputs(&github_repo)),
+                                    friendly_message: Some(format!(
+                                        "Created GitHub repository {}/{} as a fork of {}",
+                                        owner, repo, fork_of
+                                    )),
+                                }),
+                                Err(e) => bail!(
+                                    "Forked {} into {}/{}, but failed to apply its settings: {:#?}",
+                                    fork_of,
+                                    owner,
+                                    repo,
+                                    e
+                                ),
+                            };
+                        }
+
+                        if let Some(template) = &repo_config.template {
+                            let Some((template_owner, template_repo)) = template.repository.split_once('/') else {
+                                bail!(
+                                    "Invalid template repository {:?} for {}/{}: expected \"owner/repo\"",
+                                    template.repository,
+                                    owner,
+                                    repo
+                                );
+                            };
+
+                            let generate_request = GenerateRepositoryRequest {
+                                owner: owner.clone(),
+                                name: repo.clone(),
+                                description: repo_config.description.clone(),
+                                include_all_branches: template.include_all_branches,
+                                private: repo_config.private,
+                            };
+
+                            if let Err(e) = client
+                                .generate_repository(template_owner, template_repo, &generate_request)
+                                .await
+                            {
+                                bail!(
+                                    "Failed to create repository {}/{} from template {}: {}",
+                                    owner,
+                                    repo,
+                                    template.repository,
+                                    e
+                                );
+                            }
+
+                            // The generate endpoint only takes a handful of settings, so apply the rest afterwards.
+                            let update_request = post_creation_settings(&repo_config);
+
+                            return match client.update_repository(owner, repo, &update_request).await {
+                                Ok(github_repo) => Ok(OpExecResponse {
+                                    outputs: Some(repository_outputs(&github_repo)),
+                                    friendly_message: Some(format!(
+                                        "Created GitHub repository {}/{} from template {}",
+                                        owner, repo, template.repository
+                                    )),
+                                }),
+                                Err(e) => bail!(
+                                    "Created repository {}/{} from template {}, but failed to apply its settings: {:#?}",
+                                    owner,
+                                    repo,
+                                    template.repository,
+                                    e
+                                ),
+                            };
+                        }
+
                         let create_request = CreateRepositoryRequest {
                             name: repo.clone(),
                             description: repo_config.description.clone(),
@@ -40,49 +561,222 @@ impl GitHubConnector {
                             allow_auto_merge: repo_config.allow_auto_merge,
                             delete_branch_on_merge: repo_config.delete_branch_on_merge,
                             default_branch: Some(repo_config.default_branch.clone()),
+                            is_template: repo_config.is_template,
+                            auto_init: repo_config.auto_init,
+                            gitignore_template: repo_config.gitignore_template.clone(),
+                            license_template: repo_config.license_template.clone(),
                         };
 
                         match client.create_repository(owner, &create_request).await {
-                            Ok(_) => Ok(OpExecResponse {
-                                outputs: None,
+                            Ok(github_repo) => Ok(OpExecResponse {
+                                outputs: Some(repository_outputs(&github_repo)),
                                 friendly_message: Some(format!("Created GitHub repository {}/{}", owner, repo)),
                             }),
                             Err(e) => bail!("Failed to create repository {}/{}: {}", owner, repo, e),
                         }
                     }
-                    GitHubConnectorOp::UpdateRepository(new_config) => {
+                    GitHubConnectorOp::UpdateRepository(update_request) => {
+                        match client.update_repository(owner, repo, &update_request).await {
+                            Ok(github_repo) => Ok(OpExecResponse {
+                                outputs: Some(repository_outputs(&github_repo)),
+                                friendly_message: Some(format!("Updated GitHub repository {}/{}", owner, repo)),
+                            }),
+                            Err(e) => bail!("Failed to update repository {}/{}: {:#?}", owner, repo, e),
+                        }
+                    }
+                    GitHubConnectorOp::RenameRepository(from_repo) => {
+                        // The old name redirects to the new one once the rename has gone through.
+                        if let Ok(existing) = client.repos(owner, repo).get().await
+                            && is_same_repository(&existing, owner, repo)
+                        {
+                            return Ok(OpExecResponse {
+                                outputs: Some(repository_outputs(&existing)),
+                                friendly_message: Some(format!(
+                                    "Repository {}/{} was already renamed to {}",
+                                    owner, from_repo, repo
+                                )),
+                            });
+                        }
+
+                        let update_request = UpdateRepositoryRequest {
+                            name: Some(repo.clone()),
+                            ..Default::default()
+                        };
+
+                        match client.update_repository(owner, &from_repo, &update_request).await {
+                            Ok(github_repo) => Ok(OpExecResponse {
+                                outputs: Some(repository_outputs(&github_repo)),
+                                friendly_message: Some(format!("Renamed GitHub repository {}/{} to {}", owner, from_repo, repo)),
+                            }),
+                            Err(e) => bail!("Failed to rename repository {}/{} to {}: {:#?}", owner, from_repo, repo, e),
+                        }
+                    }
+                    GitHubConnectorOp::TransferRepository(from_owner, from_repo) => {
+                        if self.do_get(&addr.to_path_buf()).await?.is_some() {
+                            return no_op(format!(
+                                "Repository {}/{} was already transferred to {}/{}",
+                                from_owner, from_repo, owner, repo
+                            ));
+                        }
+
+                        let transfer_request = TransferRepositoryRequest {
+                            new_owner: owner.clone(),
+                            new_name: Some(repo.clone()),
+                        };
+
+                        if let Err(e) = client.transfer_repository(&from_owner, &from_repo, &transfer_request).await {
+                            bail!(
+                                "Failed to transfer repository {}/{} to {}/{}: {:#?}",
+                                from_owner,
+                                from_repo,
+                                owner,
+                                repo,
+                                e
+                            );
+                        }
+
+                        // Transfers complete asynchronously, much like forks.
+                        let github_repo = wait_for_repository(&client, owner, repo, FORK_TIMEOUT).await?;
+
+                        Ok(OpExecResponse {
+                            outputs: Some(repository_outputs(&github_repo)),
+                            friendly_message: Some(format!(
+                                "Transferred GitHub repository {}/{} to {}/{}",
+                                from_owner, from_repo, owner, repo
+                            )),
+                        })
+                    }
+                    GitHubConnectorOp::ArchiveRepository => {
                         let update_request = UpdateRepositoryRequest {
-                            name: None, // Can't rename via this API
-                            description: new_config.description.clone(),
-                            homepage: new_config.homepage.clone(),
-                            private: Some(new_config.private),
-                            has_issues: Some(new_config.has_issues),
-                            has_projects: Some(new_config.has_projects),
-                            has_wiki: Some(new_config.has_wiki),
-                            allow_squash_merge: Some(new_config.allow_squash_merge),
-                            allow_merge_commit: Some(new_config.allow_merge_commit),
-                            allow_rebase_merge: Some(new_config.allow_rebase_merge),
-                            allow_auto_merge: Some(new_config.allow_auto_merge),
-                            delete_branch_on_merge: Some(new_config.delete_branch_on_merge),
-                            default_branch: Some(new_config.default_branch.clone()),
-                            archived: Some(new_config.archived),
+                            archived: Some(true),
+                            ..Default::default()
                         };
 
                         match client.update_repository(owner, repo, &update_request).await {
                             Ok(_) => Ok(OpExecResponse {
                                 outputs: None,
-                                friendly_message: Some(format!("Updated GitHub repository {}/{}", owner, repo)),
+                                friendly_message: Some(format!("Archived GitHub repository {}/{}", owner, repo)),
                             }),
-                            Err(e) => bail!("Failed to update repository {}/{}: {:#?}", owner, repo, e),
+                            Err(e) => bail!("Failed to archive repository {}/{}: {:#?}", owner, repo, e),
+                        }
+                    }
+                    GitHubConnectorOp::IgnoreCreationOptions => no_op(format!(
+                        "Repository {}/{} already exists, so its creation-only options are ignored",
+                        owner, repo
+                    )),
+                    GitHubConnectorOp::SetTopics(topics) => {
+                        let request = ReplaceTopicsRequest { names: topics.clone() };
+
+                        match client.replace_topics(owner, repo, &request).await {
+                            Ok(_) => Ok(OpExecResponse {
+                                outputs: None,
+                                friendly_message: Some(format!(
+                                    "Set topics on GitHub repository {}/{} to {:?}",
+                                    owner, repo, topics
+                                )),
+                            }),
+                            Err(e) => bail!("Failed to set topics on repository {}/{}: {:#?}", owner, repo, e),
+                        }
+                    }
+                    GitHubConnectorOp::RenameBranch(from, to) => {
+                        // A retried rename finds the branch already under its new name.
+                        match client.get_branch(owner, repo, &from).await {
+                            Err(e) if is_not_found(&e) => {
+                                if client.get_branch(owner, repo, &to).await.is_ok() {
+                                    return no_op(format!("Branch {} of {}/{} is already renamed to {}", from, owner, repo, to));
+                                }
+                                bail!("Neither branch {} nor {} exists in {}/{}", from, to, owner, repo);
+                            }
+                            Err(e) => bail!("Failed to look up branch {} of {}/{}: {:#?}", from, owner, repo, e),
+                            Ok(_) => {}
+                        }
+
+                        let rename_request = RenameBranchRequest { new_name: to.clone() };
+
+                        match client.rename_branch(owner, repo, &from, &rename_request).await {
+                            Ok(_) => Ok(OpExecResponse {
+                                outputs: None,
+                                friendly_message: Some(format!(
+                                    "Renamed branch {} of GitHub repository {}/{} to {}",
+                                    from, owner, repo, to
+                                )),
+                            }),
+                            Err(e) => bail!("Failed to rename branch {} of {}/{} to {}: {:#?}", from, owner, repo, to, e),
+                        }
+                    }
+                    GitHubConnectorOp::SetCustomPropertyValues(values) => {
+                        let request = SetCustomPropertyValuesRequest {
+                            properties: values
+                                .iter()
+                                .map(|(name, value)| GitHubCustomPropertyValue {
+                                    property_name: name.clone(),
+                                    value: value.clone(),
+                                })
+                                .collect(),
+                        };
+
+                        match client.set_custom_property_values(owner, repo, &request).await {
+                            Ok(_) => Ok(OpExecResponse {
+                                outputs: None,
+                                friendly_message: Some(format!(
+                                    "Set custom properties {:?} on GitHub repository {}/{}",
+                                    values.keys().collect::<Vec<_>>(),
+                                    owner,
+                                    repo
+                                )),
+                            }),
+                            Err(e) => bail!("Failed to set custom properties on repository {}/{}: {:#?}", owner, repo, e),
+                        }
+                    }
+                    GitHubConnectorOp::UnarchiveRepository => {
+                        let update_request = UpdateRepositoryRequest {
+                            archived: Some(false),
+                            ..Default::default()
+                        };
+
+                        match client.update_repository(owner, repo, &update_request).await {
+                            Ok(_) => Ok(OpExecResponse {
+                                outputs: None,
+                                friendly_message: Some(format!("Unarchived GitHub repository {}/{}", owner, repo)),
+                            }),
+                            Err(e) => bail!("Failed to unarchive repository {}/{}: {:#?}", owner, repo, e),
+                        }
+                    }
+                    GitHubConnectorOp::ForBranch(branch, branch_op) => {
+                        self.exec_branch_protection_op(owner, repo, &branch, *branch_op).await
+                    }
+                    GitHubConnectorOp::ForBranchRule(pattern, rule_op) => {
+                        self.exec_branch_rule_op(owner, repo, &pattern, *rule_op).await
+                    }
+                    GitHubConnectorOp::DeleteRepository => {
+                        if self.do_get(&addr.to_path_buf()).await?.is_none() {
+                            return no_op(format!("Repository {}/{} is already deleted", owner, repo));
+                        }
+
+                        let backup_path = match self.backup_repository(owner, repo).await {
+                            Ok(backup_path) => backup_path,
+                            Err(e) => bail!("Refusing to delete repository {}/{}: backup failed: {:#}", owner, repo, e),
+                        };
+
+                        match client.delete_repository(owner, repo).await {
+                            Ok(_) => Ok(OpExecResponse {
+                                outputs: Some(HashMap::from([(
+                                    String::from("backup_path"),
+                                    Some(backup_path.display().to_string()),
+                                )])),
+                                friendly_message: Some(format!(
+                                    "Deleted GitHub repository {}/{} (backed up to {})",
+                                    owner,
+                                    repo,
+                                    backup_path.display()
+                                )),
+                            }),
+                            Err(e) if is_not_found(&e) => {
+                                no_op(format!("Repository {}/{} is already deleted", owner, repo))
+                            }
+                            Err(e) => bail!("Failed to delete repository {}/{}: {:#?}", owner, repo, e),
                         }
                     }
-                    GitHubConnectorOp::DeleteRepository => match client.delete_repository(owner, repo).await {
-                        Ok(_) => Ok(OpExecResponse {
-                            outputs: None,
-                            friendly_message: Some(format!("Deleted GitHub repository {}/{}", owner, repo)),
-                        }),
-                        Err(e) => bail!("Failed to delete repository {}/{}: {:#?}", owner, repo, e),
-                    },
                     GitHubConnectorOp::AddCollaborator(principal, role) => match principal {
                         crate::resource::CollaboratorPrincipal::User(username) => {
                             let request = AddCollaboratorRequest {
@@ -99,7 +793,19 @@ impl GitHubConnector {
                                         repo
                                     )),
                                 }),
-                                Err(e) => bail!("Failed to add user {} to repository {}/{}: {:#?}", username, owner, repo, e),
+                                Err(e) => {
+                                    let collaborators = client.list_repo_collaborators(owner, repo, Some("direct")).await?;
+                                    if collaborators.get(&CollaboratorPrincipal::User(username.clone())) == Some(&role) {
+                                        return no_op(format!(
+                                            "User {} is already {} on repository {}/{}",
+                                            username,
+                                            role.to_string(),
+                                            owner,
+                                            repo
+                                        ));
+                                    }
+                                    bail!("Failed to add user {} to repository {}/{}: {:#?}", username, owner, repo, e)
+                                }
                             }
                         }
                         crate::resource::CollaboratorPrincipal::Team(team_slug) => {
@@ -181,6 +887,10 @@ impl GitHubConnector {
                                         username, owner, repo
                                     )),
                                 }),
+                                Err(e) if is_not_found(&e) => no_op(format!(
+                                    "User {} is not a collaborator on repository {}/{}",
+                                    username, owner, repo
+                                )),
                                 Err(e) => bail!(
                                     "Failed to remove user {} from repository {}/{}: {:#?}",
                                     username,
@@ -199,6 +909,10 @@ impl GitHubConnector {
                                         team_slug, owner, repo
                                     )),
                                 }),
+                                Err(e) if is_not_found(&e) => no_op(format!(
+                                    "Team {} has no access to repository {}/{}",
+                                    team_slug, owner, repo
+                                )),
                                 Err(e) => bail!(
                                     "Failed to remove team {} from repository {}/{}: {:#?}",
                                     team_slug,
@@ -213,154 +927,1014 @@ impl GitHubConnector {
                 }
             }
             GitHubResourceAddress::BranchProtection { owner, repo, branch } => {
+                self.exec_branch_protection_op(owner, repo, branch, op).await
+            }
+            GitHubResourceAddress::Branch { owner, repo, branch } => self.exec_branch_op(owner, repo, branch, op).await,
+            GitHubResourceAddress::BranchRule { owner, repo, pattern } => {
+                self.exec_branch_rule_op(owner, repo, pattern, op).await
+            }
+            GitHubResourceAddress::ActionsPermissions { owner, repo } => {
+                let client = self.client.read().await.clone();
+                let scope = ActionsScope::Repository { owner, repo };
+
+                match op {
+                    GitHubConnectorOp::SetActionsPermissions(permissions) => {
+                        let request = GitHubActionsPermissions {
+                            enabled: Some(permissions.enabled),
+                            enabled_repositories: None,
+                            allowed_actions: permissions.enabled.then(|| permissions.allowed_actions.to_string()),
+                        };
+
+                        if let Err(e) = client.set_actions_permissions(&scope, &request).await {
+                            bail!("Failed to set GitHub Actions permissions for {}/{}: {:#?}", owner, repo, e);
+                        }
+
+                        apply_actions_policy(
+                            &client,
+                            &scope,
+                            permissions.selected_actions.as_ref(),
+                            &permissions.workflow_permissions,
+                        )
+                        .await
+                        .with_context(|| {
+                            format!(
+                                "Set GitHub Actions permissions for {}/{}, but the op only partly completed",
+                                owner, repo
+                            )
+                        })?;
+
+                        Ok(OpExecResponse {
+                            outputs: None,
+                            friendly_message: Some(format!("Set GitHub Actions permissions for {}/{}", owner, repo)),
+                        })
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            GitHubResourceAddress::OrgActionsPermissions { org } => {
                 let client = self.client.read().await.clone();
+                let scope = ActionsScope::Organization { org };
 
                 match op {
-                    GitHubConnectorOp::CreateBranchProtection(protection_config) => {
-                        let create_request = CreateBranchProtectionRequest {
-                            required_status_checks: protection_config.required_status_checks.as_ref().map(|checks| {
-                                crate::github_ext::GitHubRequiredStatusChecks {
-                                    strict: checks.strict,
-                                    contexts: checks.contexts.clone(),
+                    GitHubConnectorOp::SetOrgActionsPermissions(permissions) => {
+                        let enabled = permissions.enabled_repositories != EnabledRepositories::Disabled;
+                        let request = GitHubActionsPermissions {
+                            enabled: None,
+                            enabled_repositories: Some(permissions.enabled_repositories.to_string()),
+                            allowed_actions: enabled.then(|| permissions.allowed_actions.to_string()),
+                        };
+
+                        if let Err(e) = client.set_actions_permissions(&scope, &request).await {
+                            bail!("Failed to set GitHub Actions permissions for organization {}: {:#?}", org, e);
+                        }
+
+                        if let EnabledRepositories::Selected(repos) = &permissions.enabled_repositories {
+                            let selected_repository_ids = repository_ids(&client, org, repos)
+                                .await
+                                .context("Failed to look up the repositories to enable Actions for")?;
+
+                            let request = SetSelectedRepositoriesRequest { selected_repository_ids };
+                            if let Err(e) = client.set_actions_enabled_repositories(org, &request).await {
+                                bail!(
+                                    "Set GitHub Actions permissions for organization {}, but failed to set the repositories \
+                                     enabled for Actions, so the op only partly completed: {:#?}",
+                                    org,
+                                    e
+                                );
+                            }
+                        }
+
+                        apply_actions_policy(
+                            &client,
+                            &scope,
+                            permissions.selected_actions.as_ref(),
+                            &permissions.workflow_permissions,
+                        )
+                        .await
+                        .with_context(|| {
+                            format!(
+                                "Set GitHub Actions permissions for organization {}, but the op only partly completed",
+                                org
+                            )
+                        })?;
+
+                        Ok(OpExecResponse {
+                            outputs: None,
+                            friendly_message: Some(format!("Set GitHub Actions permissions for organization {}", org)),
+                        })
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            GitHubResourceAddress::RunnerGroup { org, name } => self.exec_runner_group_op(org, name, op).await,
+            GitHubResourceAddress::Pages { owner, repo } => self.exec_pages_op(owner, repo, op).await,
+            GitHubResourceAddress::InteractionLimits { owner, repo } => {
+                let scope = InteractionLimitsScope::Repository { owner, repo };
+                let subject = format!("{}/{}", owner, repo);
+                self.exec_interaction_limits_op(&scope, &subject, &addr, op).await
+            }
+            GitHubResourceAddress::OrgInteractionLimits { org } => {
+                let scope = InteractionLimitsScope::Organization { org };
+                let subject = format!("organization {}", org);
+                self.exec_interaction_limits_op(&scope, &subject, &addr, op).await
+            }
+            GitHubResourceAddress::CodeScanning { owner, repo } => {
+                let client = self.client.read().await.clone();
+
+                match op {
+                    GitHubConnectorOp::SetCodeScanningDefaultSetup(setup) => {
+                        let configured = setup.state == CodeScanningState::Configured;
+                        let request = UpdateCodeScanningDefaultSetupRequest {
+                            state: setup.state.to_string(),
+                            query_suite: configured.then(|| setup.query_suite.to_string()),
+                            languages: (configured && !setup.languages.is_empty()).then_some(setup.languages),
+                        };
+
+                        let run = match client.update_code_scanning_default_setup(owner, repo, &request).await {
+                            Ok(run) => run,
+                            Err(e) => bail!("Failed to set code scanning default setup for {}/{}: {:#?}", owner, repo, e),
+                        };
+
+                        let mut outputs = HashMap::new();
+                        let mut friendly_message = format!("Set code scanning default setup for {}/{}", owner, repo);
+
+                        // The setup is configured by a workflow run, which finishes after the request returns.
+                        if let Some(run_id) = run.run_id {
+                            outputs.insert(String::from("run_id"), Some(run_id.to_string()));
+                            outputs.insert(String::from("run_url"), run.run_url);
+
+                            let timeout = Duration::from_secs(self.config.read().await.code_scanning_timeout_secs);
+                            let finished = match wait_for_workflow_run(&client, owner, repo, run_id, timeout).await {
+                                Ok(finished) => finished,
+                                Err(e) => bail!(
+                                    "Set code scanning default setup for {}/{}, but failed to wait for it to finish configuring, so the op only partly completed: {:#}",
+                                    owner,
+                                    repo,
+                                    e
+                                ),
+                            };
+
+                            match finished {
+                                Some(finished) if finished.conclusion.as_deref() == Some("success") => {}
+                                Some(finished) => bail!(
+                                    "Code scanning default setup for {}/{} didn't finish configuring (conclusion: {}). See {}",
+                                    owner,
+                                    repo,
+                                    finished.conclusion.unwrap_or_default(),
+                                    finished.html_url.unwrap_or_default()
+                                ),
+                                None => {
+                                    friendly_message = format!(
+                                        "{}, but it was still configuring after {}s",
+                                        friendly_message,
+                                        timeout.as_secs()
+                                    );
                                 }
-                            }),
-                            enforce_admins: protection_config.enforce_admins,
-                            required_pull_request_reviews: protection_config.required_pull_request_reviews.as_ref().map(
-                                |reviews| crate::github_ext::GitHubPullRequestReviewEnforcement {
-                                    required_approving_review_count: Some(reviews.required_approving_review_count),
-                                    dismiss_stale_reviews: Some(reviews.dismiss_stale_reviews),
-                                    require_code_owner_reviews: Some(reviews.require_code_owner_reviews),
-                                    require_last_push_approval: Some(reviews.require_last_push_approval),
-                                },
+                            }
+                        }
+
+                        match client.get_code_scanning_default_setup(owner, repo).await {
+                            Ok(final_setup) => {
+                                outputs.insert(String::from("state"), Some(final_setup.state));
+                                outputs.insert(String::from("languages"), Some(final_setup.languages.join(",")));
+                                outputs.insert(String::from("query_suite"), final_setup.query_suite);
+                                outputs.insert(String::from("updated_at"), final_setup.updated_at);
+                            }
+                            Err(e) => tracing::warn!(
+                                "Failed to read back code scanning default setup for {}/{}: {:#?}",
+                                owner,
+                                repo,
+                                e
                             ),
-                            restrictions: protection_config.restrictions.as_ref().map(|restrictions| {
-                                crate::github_ext::GitHubBranchRestrictions {
-                                    users: restrictions
-                                        .users
-                                        .iter()
-                                        .map(|u| crate::github_ext::GitHubUser { login: u.clone() })
-                                        .collect(),
-                                    teams: restrictions
-                                        .teams
-                                        .iter()
-                                        .map(|t| crate::github_ext::GitHubTeam { name: t.clone() })
-                                        .collect(),
-                                    apps: restrictions
-                                        .apps
-                                        .iter()
-                                        .map(|a| crate::github_ext::GitHubApp { name: a.clone() })
-                                        .collect(),
-                                }
-                            }),
-                            required_linear_history: Some(protection_config.required_linear_history),
-                            allow_force_pushes: Some(protection_config.allow_force_pushes),
-                            allow_deletions: Some(protection_config.allow_deletions),
-                            block_creations: Some(protection_config.block_creations),
-                            required_conversation_resolution: Some(protection_config.required_conversation_resolution),
-                            lock_branch: Some(protection_config.lock_branch),
-                            allow_fork_syncing: Some(protection_config.allow_fork_syncing),
+                        }
+
+                        Ok(OpExecResponse {
+                            outputs: Some(outputs),
+                            friendly_message: Some(friendly_message),
+                        })
+                    }
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            GitHubResourceAddress::ActionsSettings { owner, repo } => {
+                let client = self.client.read().await.clone();
+                let scope = ActionsScope::Repository { owner, repo };
+
+                match op {
+                    GitHubConnectorOp::SetArtifactRetention(days) => {
+                        if let Err(e) = client.set_artifact_retention(&scope, &GitHubArtifactRetention { days }).await {
+                            bail!("Failed to set artifact and log retention for {}/{}: {:#?}", owner, repo, e);
+                        }
+
+                        Ok(OpExecResponse {
+                            outputs: None,
+                            friendly_message: Some(format!(
+                                "Set artifact and log retention for {}/{} to {} days",
+                                owner, repo, days
+                            )),
+                        })
+                    }
+                    GitHubConnectorOp::SetForkPrApproval(approval) => {
+                        let request = GitHubForkPrApproval {
+                            approval_policy: approval.to_string(),
                         };
 
-                        match client.create_branch_protection(owner, repo, branch, &create_request).await {
-                            Ok(_) => Ok(OpExecResponse {
-                                outputs: None,
-                                friendly_message: Some(format!(
-                                    "Created branch protection for {}/{} branch {}",
-                                    owner, repo, branch
-                                )),
-                            }),
-                            Err(e) => bail!(
-                                "Failed to create branch protection for {}/{} branch {}: {:#?}",
+                        if let Err(e) = client.set_fork_pr_approval(&scope, &request).await {
+                            bail!(
+                                "Failed to set the fork pull request approval policy for {}/{}: {:#?}",
                                 owner,
                                 repo,
-                                branch,
                                 e
-                            ),
+                            );
                         }
+
+                        Ok(OpExecResponse {
+                            outputs: None,
+                            friendly_message: Some(format!(
+                                "Set the fork pull request approval policy for {}/{} to {}",
+                                owner,
+                                repo,
+                                approval.to_string()
+                            )),
+                        })
                     }
-                    GitHubConnectorOp::UpdateBranchProtection(new_config) => {
-                        let update_request = CreateBranchProtectionRequest {
-                            required_status_checks: new_config.required_status_checks.as_ref().map(|checks| {
-                                crate::github_ext::GitHubRequiredStatusChecks {
-                                    strict: checks.strict,
-                                    contexts: checks.contexts.clone(),
-                                }
-                            }),
-                            enforce_admins: new_config.enforce_admins,
-                            required_pull_request_reviews: new_config.required_pull_request_reviews.as_ref().map(|reviews| {
-                                crate::github_ext::GitHubPullRequestReviewEnforcement {
-                                    required_approving_review_count: Some(reviews.required_approving_review_count),
-                                    dismiss_stale_reviews: Some(reviews.dismiss_stale_reviews),
-                                    require_code_owner_reviews: Some(reviews.require_code_owner_reviews),
-                                    require_last_push_approval: Some(reviews.require_last_push_approval),
-                                }
-                            }),
-                            restrictions: new_config.restrictions.as_ref().map(|restrictions| {
-                                crate::github_ext::GitHubBranchRestrictions {
-                                    users: restrictions
-                                        .users
-                                        .iter()
-                                        .map(|u| crate::github_ext::GitHubUser { login: u.clone() })
-                                        .collect(),
-                                    teams: restrictions
-                                        .teams
-                                        .iter()
-                                        .map(|t| crate::github_ext::GitHubTeam { name: t.clone() })
-                                        .collect(),
-                                    apps: restrictions
-                                        .apps
-                                        .iter()
-                                        .map(|a| crate::github_ext::GitHubApp { name: a.clone() })
-                                        .collect(),
-                                }
-                            }),
-                            required_linear_history: Some(new_config.required_linear_history),
-                            allow_force_pushes: Some(new_config.allow_force_pushes),
-                            allow_deletions: Some(new_config.allow_deletions),
-                            block_creations: Some(new_config.block_creations),
-                            required_conversation_resolution: Some(new_config.required_conversation_resolution),
-                            lock_branch: Some(new_config.lock_branch),
-                            allow_fork_syncing: Some(new_config.allow_fork_syncing),
+                    GitHubConnectorOp::SetActionsAccessLevel(access_level) => {
+                        let request = GitHubActionsAccess {
+                            access_level: access_level.to_string(),
                         };
 
-                        match client.update_branch_protection(owner, repo, branch, &update_request).await {
-                            Ok(_) => Ok(OpExecResponse {
-                                outputs: None,
-                                friendly_message: Some(format!(
-                                    "Updated branch protection for {}/{} branch {}",
-                                    owner, repo, branch
-                                )),
-                            }),
-                            Err(e) => bail!(
-                                "Failed to update branch protection for {}/{} branch {}: {:#?}",
+                        if let Err(e) = client.set_actions_access(&scope, &request).await {
+                            bail!("Failed to set the Actions access level for {}/{}: {:#?}", owner, repo, e);
+                        }
+
+                        Ok(OpExecResponse {
+                            outputs: None,
+                            friendly_message: Some(format!(
+                                "Set the Actions access level for {}/{} to {}",
+                                owner,
+                                repo,
+                                access_level.to_string()
+                            )),
+                        })
+                    }
+                    GitHubConnectorOp::SetOidcSubjectClaim(claim) => {
+                        let request = GitHubOidcSubjectClaim {
+                            use_default: claim.use_default,
+                            include_claim_keys: (!claim.use_default).then_some(claim.include_claim_keys),
+                        };
+
+                        if let Err(e) = client.set_oidc_subject_claim(&scope, &request).await {
+                            bail!(
+                                "Failed to set the OIDC subject claim template for {}/{}: {:#?}",
                                 owner,
                                 repo,
-                                branch,
                                 e
-                            ),
+                            );
                         }
+
+                        Ok(OpExecResponse {
+                            outputs: None,
+                            friendly_message: Some(format!("Set the OIDC subject claim template for {}/{}", owner, repo)),
+                        })
                     }
-                    GitHubConnectorOp::DeleteBranchProtection => {
-                        match client.delete_branch_protection(owner, repo, branch).await {
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+            GitHubResourceAddress::CustomProperties { org } => {
+                let client = self.client.read().await.clone();
+
+                match op {
+                    GitHubConnectorOp::UpsertCustomProperties(properties) => {
+                        let request = UpsertCustomPropertiesRequest {
+                            properties: properties
+                                .iter()
+                                .map(|(name, property)| custom_property_request(name, property))
+                                .collect(),
+                        };
+
+                        match client.upsert_custom_properties(org, &request).await {
                             Ok(_) => Ok(OpExecResponse {
                                 outputs: None,
                                 friendly_message: Some(format!(
-                                    "Removed branch protection for {}/{} branch {}",
-                                    owner, repo, branch
+                                    "Set custom property definitions {:?} in organization {}",
+                                    properties.keys().collect::<Vec<_>>(),
+                                    org
                                 )),
                             }),
-                            Err(e) => bail!(
-                                "Failed to remove branch protection for {}/{} branch {}: {:#?}",
+                            Err(e) => bail!("Failed to set custom property definitions in organization {}: {:#?}", org, e),
+                        }
+                    }
+                    GitHubConnectorOp::DeleteCustomProperty(name) => match client.delete_custom_property(org, &name).await {
+                        Ok(_) => Ok(OpExecResponse {
+                            outputs: None,
+                            friendly_message: Some(format!("Deleted custom property {} from organization {}", name, org)),
+                        }),
+                        Err(e) if is_not_found(&e) => {
+                            no_op(format!("Custom property {} doesn't exist in organization {}", name, org))
+                        }
+                        Err(e) => bail!(
+                            "Failed to delete custom property {} from organization {}: {:#?}",
+                            name,
+                            org,
+                            e
+                        ),
+                    },
+                    _ => Err(invalid_op(&addr, &op)),
+                }
+            }
+        }
+    }
+
+    /// Execute a branch protection op, either at its own address or forwarded from the repository's plan.
+    async fn exec_branch_protection_op(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        op: GitHubConnectorOp,
+    ) -> anyhow::Result<OpExecResponse> {
+        let client = self.client.read().await.clone();
+
+        match op {
+            GitHubConnectorOp::CreateBranchProtection(protection_config) => {
+                // Protection can only be applied to a branch that exists, and the branch's own op may not have run yet.
+                self.ensure_declared_branch(owner, repo, branch).await?;
+
+                let create_request = CreateBranchProtectionRequest {
+                    required_status_checks: protection_config.required_status_checks.as_ref().map(status_checks_request),
+                    enforce_admins: protection_config.enforce_admins,
+                    required_pull_request_reviews: protection_config.required_pull_request_reviews.as_ref().map(
+                        |reviews| crate::github_ext::GitHubPullRequestReviewEnforcement {
+                            required_approving_review_count: Some(reviews.required_approving_review_count),
+                            dismiss_stale_reviews: Some(reviews.dismiss_stale_reviews),
+                            require_code_owner_reviews: Some(reviews.require_code_owner_reviews),
+                            require_last_push_approval: Some(reviews.require_last_push_approval),
+                            dismissal_restrictions: review_allowances_request(reviews.dismissal_restrictions.as_ref(), false),
+                            bypass_pull_request_allowances: review_allowances_request(
+                                reviews.bypass_pull_request_allowances.as_ref(),
+                                false,
+                            ),
+                        },
+                    ),
+                    restrictions: protection_config.restrictions.as_ref().map(branch_restrictions_request),
+                    required_linear_history: Some(protection_config.required_linear_history),
+                    allow_force_pushes: Some(protection_config.allow_force_pushes),
+                    allow_deletions: Some(protection_config.allow_deletions),
+                    block_creations: Some(protection_config.block_creations),
+                    required_conversation_resolution: Some(protection_config.required_conversation_resolution),
+                    lock_branch: Some(protection_config.lock_branch),
+                    allow_fork_syncing: Some(protection_config.allow_fork_syncing),
+                };
+
+                match client.create_branch_protection(owner, repo, branch, &create_request).await {
+                    Ok(_) => {
+                        if let Err(e) =
+                            set_required_signatures(&client, owner, repo, branch, protection_config.required_signatures).await
+                        {
+                            bail!(
+                                "Created branch protection for {}/{} branch {}, but failed to set required signatures, so the op only partly completed: {:#}",
                                 owner,
                                 repo,
                                 branch,
                                 e
+                            );
+                        }
+
+                        Ok(OpExecResponse {
+                            outputs: None,
+                            friendly_message: Some(format!(
+                                "Created branch protection for {}/{} branch {}",
+                                owner, repo, branch
+                            )),
+                        })
+                    }
+                    Err(e) => bail!(
+                        "Failed to create branch protection for {}/{} branch {}: {:#?}",
+                        owner,
+                        repo,
+                        branch,
+                        e
+                    ),
+                }
+            }
+            GitHubConnectorOp::UpdateBranchProtection(new_config) => {
+                // Review restrictions only exist on organization repositories, so only there can one be lifted.
+                let clear_review_allowances = self.config.read().await.orgs.iter().any(|org| org == owner);
+
+                let update_request = CreateBranchProtectionRequest {
+                    required_status_checks: new_config.required_status_checks.as_ref().map(status_checks_request),
+                    enforce_admins: new_config.enforce_admins,
+                    required_pull_request_reviews: new_config.required_pull_request_reviews.as_ref().map(|reviews| {
+                        crate::github_ext::GitHubPullRequestReviewEnforcement {
+                            required_approving_review_count: Some(reviews.required_approving_review_count),
+                            dismiss_stale_reviews: Some(reviews.dismiss_stale_reviews),
+                            require_code_owner_reviews: Some(reviews.require_code_owner_reviews),
+                            require_last_push_approval: Some(reviews.require_last_push_approval),
+                            dismissal_restrictions: review_allowances_request(
+                                reviews.dismissal_restrictions.as_ref(),
+                                clear_review_allowances,
+                            ),
+                            bypass_pull_request_allowances: review_allowances_request(
+                                reviews.bypass_pull_request_allowances.as_ref(),
+                                clear_review_allowances,
                             ),
                         }
+                    }),
+                    restrictions: new_config.restrictions.as_ref().map(branch_restrictions_request),
+                    required_linear_history: Some(new_config.required_linear_history),
+                    allow_force_pushes: Some(new_config.allow_force_pushes),
+                    allow_deletions: Some(new_config.allow_deletions),
+                    block_creations: Some(new_config.block_creations),
+                    required_conversation_resolution: Some(new_config.required_conversation_resolution),
+                    lock_branch: Some(new_config.lock_branch),
+                    allow_fork_syncing: Some(new_config.allow_fork_syncing),
+                };
+
+                match client.update_branch_protection(owner, repo, branch, &update_request).await {
+                    Ok(_) => {
+                        if let Err(e) =
+                            set_required_signatures(&client, owner, repo, branch, new_config.required_signatures).await
+                        {
+                            bail!(
+                                "Updated branch protection for {}/{} branch {}, but failed to set required signatures, so the op only partly completed: {:#}",
+                                owner,
+                                repo,
+                                branch,
+                                e
+                            );
+                        }
+
+                        Ok(OpExecResponse {
+                            outputs: None,
+                            friendly_message: Some(format!(
+                                "Updated branch protection for {}/{} branch {}",
+                                owner, repo, branch
+                            )),
+                        })
                     }
-                    _ => Err(invalid_op(&addr, &op)),
+                    Err(e) => bail!(
+                        "Failed to update branch protection for {}/{} branch {}: {:#?}",
+                        owner,
+                        repo,
+                        branch,
+                        e
+                    ),
+                }
+            }
+            GitHubConnectorOp::DeleteBranchProtection => {
+                match client.delete_branch_protection(owner, repo, branch).await {
+                    Ok(_) => Ok(OpExecResponse {
+                        outputs: None,
+                        friendly_message: Some(format!(
+                            "Removed branch protection for {}/{} branch {}",
+                            owner, repo, branch
+                        )),
+                    }),
+                    Err(e) if is_not_found(&e) => no_op(format!(
+                        "Branch {} of {}/{} is already unprotected",
+                        branch, owner, repo
+                    )),
+                    Err(e) => bail!(
+                        "Failed to remove branch protection for {}/{} branch {}: {:#?}",
+                        owner,
+                        repo,
+                        branch,
+                        e
+                    ),
+                }
+            }
+            _ => Err(invalid_op(
+                &GitHubResourceAddress::BranchProtection {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    branch: branch.to_string(),
+                },
+                &op,
+            )),
+        }
+    }
+
+    async fn exec_branch_op(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        op: GitHubConnectorOp,
+    ) -> anyhow::Result<OpExecResponse> {
+        let client = self.client.read().await.clone();
+
+        match op {
+            GitHubConnectorOp::CreateBranch(branch_config) => {
+                if client.get_branch(owner, repo, branch).await.is_ok() {
+                    return no_op(format!("Branch {} of {}/{} already exists", branch, owner, repo));
+                }
+
+                let sha = match client.get_commit_sha(owner, repo, &branch_config.source).await {
+                    Ok(commit) => commit.sha,
+                    Err(e) => bail!(
+                        "Failed to resolve {} in {}/{} to create branch {} from: {}",
+                        branch_config.source,
+                        owner,
+                        repo,
+                        branch,
+                        e
+                    ),
+                };
+
+                match client.create_branch(owner, repo, branch, &sha).await {
+                    Ok(_) => Ok(OpExecResponse {
+                        outputs: Some(HashMap::from([(String::from("sha"), Some(sha))])),
+                        friendly_message: Some(format!(
+                            "Created branch {} in {}/{} from {}",
+                            branch, owner, repo, branch_config.source
+                        )),
+                    }),
+                    Err(e) => bail!("Failed to create branch {} in {}/{}: {:#?}", branch, owner, repo, e),
+                }
+            }
+            GitHubConnectorOp::DeleteBranch => {
+                match client.get_branch(owner, repo, branch).await {
+                    Err(e) if is_not_found(&e) => {
+                        return no_op(format!("Branch {} of {}/{} is already deleted", branch, owner, repo));
+                    }
+                    Err(e) => bail!("Failed to look up branch {} of {}/{}: {:#?}", branch, owner, repo, e),
+                    Ok(_) => {}
+                }
+
+                // Checked again here, since pull requests may have been opened since the plan.
+                let open_pulls = client.count_open_pulls_into(owner, repo, branch).await?;
+                if open_pulls > 0 {
+                    bail!(
+                        "Branch {} of {}/{} has {} open pull request(s) targeting it, so it was not deleted",
+                        branch,
+                        owner,
+                        repo,
+                        open_pulls
+                    );
+                }
+
+                match client.delete_branch(owner, repo, branch).await {
+                    Ok(()) => Ok(OpExecResponse {
+                        outputs: None,
+                        friendly_message: Some(format!("Deleted branch {} of {}/{}", branch, owner, repo)),
+                    }),
+                    Err(e) if is_not_found(&e) => no_op(format!("Branch {} of {}/{} is already deleted", branch, owner, repo)),
+                    Err(e) => bail!("Failed to delete branch {} of {}/{}: {:#?}", branch, owner, repo, e),
+                }
+            }
+            _ => Err(invalid_op(
+                &GitHubResourceAddress::Branch {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    branch: branch.to_string(),
+                },
+                &op,
+            )),
+        }
+    }
+
+    /// Create the branch if the workspace declares it with a branch.ron and it doesn't exist yet.
+    async fn ensure_declared_branch(&self, owner: &str, repo: &str, branch: &str) -> anyhow::Result<()> {
+        let addr = GitHubResourceAddress::Branch {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            branch: branch.to_string(),
+        };
+
+        let Some(desired) = self.read_workspace_file(&addr)? else {
+            return Ok(());
+        };
+
+        let branch_config: Branch = RON.from_str(&desired)?;
+        self.exec_branch_op(owner, repo, branch, GitHubConnectorOp::CreateBranch(branch_config))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Execute a branch rule op, either at its own address or forwarded from the repository's plan.
+    async fn exec_branch_rule_op(
+        &self,
+        owner: &str,
+        repo: &str,
+        pattern: &str,
+        op: GitHubConnectorOp,
+    ) -> anyhow::Result<OpExecResponse> {
+        let client = self.client.read().await.clone();
+        let existing = client.get_branch_rule(owner, repo, pattern).await?;
+
+        match op {
+            GitHubConnectorOp::CreateBranchRule(rule_config) => {
+                // A retried create may find that GitHub already processed the first attempt.
+                if let Some(existing) = existing {
+                    let rule_id = existing.id.clone();
+                    if branch_rule_resource(existing) == rule_config {
+                        return Ok(OpExecResponse {
+                            outputs: Some(HashMap::from([(String::from("id"), Some(rule_id))])),
+                            friendly_message: Some(format!(
+                                "Branch rule for {}/{} pattern {} is already in place; nothing to do",
+                                owner, repo, pattern
+                            )),
+                        });
+                    }
+                    bail!(
+                        "A branch rule for {}/{} pattern {} already exists with different settings. Re-run the plan to update it instead.",
+                        owner,
+                        repo,
+                        pattern
+                    );
+                }
+
+                let github_repo = client.repos(owner, repo).get().await?;
+                let Some(repository_id) = github_repo.node_id else {
+                    bail!("Repository {}/{} has no node ID", owner, repo);
+                };
+
+                let mut input = self.branch_rule_input(&client, owner, &rule_config).await?;
+                input["pattern"] = pattern.into();
+
+                match client.create_branch_rule(&repository_id, input).await {
+                    Ok(rule_id) => Ok(OpExecResponse {
+                        outputs: Some(HashMap::from([(String::from("id"), Some(rule_id))])),
+                        friendly_message: Some(format!("Created branch rule for {}/{} pattern {}", owner, repo, pattern)),
+                    }),
+                    Err(e) => bail!(
+                        "Failed to create branch rule for {}/{} pattern {}: {:#}",
+                        owner,
+                        repo,
+                        pattern,
+                        e
+                    ),
+                }
+            }
+            GitHubConnectorOp::UpdateBranchRule(rule_config) => {
+                let Some(existing) = existing else {
+                    bail!(
+                        "Branch rule for {}/{} pattern {} no longer exists. Re-run the plan to create it instead.",
+                        owner,
+                        repo,
+                        pattern
+                    );
+                };
+
+                let input = self.branch_rule_input(&client, owner, &rule_config).await?;
+
+                match client.update_branch_rule(&existing.id, input).await {
+                    Ok(()) => Ok(OpExecResponse {
+                        outputs: Some(HashMap::from([(String::from("id"), Some(existing.id))])),
+                        friendly_message: Some(format!("Updated branch rule for {}/{} pattern {}", owner, repo, pattern)),
+                    }),
+                    Err(e) => bail!(
+                        "Failed to update branch rule for {}/{} pattern {}: {:#}",
+                        owner,
+                        repo,
+                        pattern,
+                        e
+                    ),
+                }
+            }
+            GitHubConnectorOp::DeleteBranchRule => {
+                let Some(existing) = existing else {
+                    return no_op(format!("Branch rule for {}/{} pattern {} is already deleted", owner, repo, pattern));
+                };
+
+                match client.delete_branch_rule(&existing.id).await {
+                    Ok(()) => Ok(OpExecResponse {
+                        outputs: None,
+                        friendly_message: Some(format!("Deleted branch rule for {}/{} pattern {}", owner, repo, pattern)),
+                    }),
+                    Err(e) => bail!(
+                        "Failed to delete branch rule for {}/{} pattern {}: {:#}",
+                        owner,
+                        repo,
+                        pattern,
+                        e
+                    ),
+                }
+            }
+            _ => Err(invalid_op(
+                &GitHubResourceAddress::BranchRule {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    pattern: pattern.to_string(),
+                },
+                &op,
+            )),
+        }
+    }
+
+    async fn exec_interaction_limits_op(
+        &self,
+        scope: &InteractionLimitsScope<'_>,
+        subject: &str,
+        addr: &GitHubResourceAddress,
+        op: GitHubConnectorOp,
+    ) -> anyhow::Result<OpExecResponse> {
+        let client = self.client.read().await.clone();
+
+        match op {
+            GitHubConnectorOp::SetInteractionLimits(limits) => {
+                let request = SetInteractionLimitsRequest {
+                    limit: limits.limit.to_string(),
+                    expiry: limits.expiry.to_string(),
+                };
+
+                match client.set_interaction_limits(scope, &request).await {
+                    Ok(applied) => Ok(OpExecResponse {
+                        friendly_message: Some(format!(
+                            "Limited interactions on {} to {} until {}",
+                            subject,
+                            request.limit,
+                            applied.expires_at.as_deref().unwrap_or(&request.expiry)
+                        )),
+                        outputs: Some(HashMap::from([(String::from("expires_at"), applied.expires_at)])),
+                    }),
+                    Err(e) => bail!("Failed to limit interactions on {}: {:#?}", subject, e),
                 }
             }
+            GitHubConnectorOp::RemoveInteractionLimits => match client.remove_interaction_limits(scope).await {
+                Ok(()) => Ok(OpExecResponse {
+                    outputs: None,
+                    friendly_message: Some(format!("Removed the interaction limit on {}", subject)),
+                }),
+                Err(e) if is_not_found(&e) => no_op(format!("The interaction limit on {} is already lifted", subject)),
+                Err(e) => bail!("Failed to remove the interaction limit on {}: {:#?}", subject, e),
+            },
+            _ => Err(invalid_op(addr, &op)),
         }
     }
+
+    async fn exec_pages_op(&self, owner: &str, repo: &str, op: GitHubConnectorOp) -> anyhow::Result<OpExecResponse> {
+        let client = self.client.read().await.clone();
+        let existing = match client.get_pages(owner, repo).await {
+            Ok(pages) => Some(pages),
+            Err(e) if is_not_found(&e) => None,
+            Err(e) => bail!("Failed to get GitHub Pages for {}/{}: {:#?}", owner, repo, e),
+        };
+
+        match op {
+            GitHubConnectorOp::CreatePages(pages_config) => {
+                // A retried create may find that GitHub already processed the first attempt.
+                if let Some(existing) = existing {
+                    let mut existing = pages_resource(existing)?;
+                    if pages_config.visibility.is_none() {
+                        existing.visibility = None;
+                    }
+                    if existing == pages_config {
+                        return no_op(format!("GitHub Pages for {}/{} is already enabled", owner, repo));
+                    }
+                    bail!(
+                        "GitHub Pages for {}/{} is already enabled with different settings. Re-run the plan to update it instead.",
+                        owner,
+                        repo
+                    );
+                }
+
+                let request = CreatePagesRequest {
+                    build_type: pages_build_type(&pages_config.source),
+                    source: pages_source_request(&pages_config.source),
+                };
+                let created = match client.create_pages(owner, repo, &request).await {
+                    Ok(created) => created,
+                    Err(e) => bail!("Failed to enable GitHub Pages for {}/{}: {:#?}", owner, repo, e),
+                };
+
+                // The domain, HTTPS and visibility can only be set once the site exists.
+                let mut friendly_message = format!("Enabled GitHub Pages for {}/{}", owner, repo);
+                if pages_config.custom_domain.is_some() || pages_config.https_enforced || pages_config.visibility.is_some() {
+                    let warning = apply_pages_update(&client, owner, repo, &pages_config)
+                        .await
+                        .with_context(|| {
+                            format!(
+                                "Enabled GitHub Pages for {}/{}, but the op only partly completed",
+                                owner, repo
+                            )
+                        })?;
+                    if let Some(warning) = warning {
+                        friendly_message = format!("{}\n{}", friendly_message, warning);
+                    }
+                }
+
+                Ok(OpExecResponse {
+                    outputs: Some(HashMap::from([(String::from("html_url"), created.html_url)])),
+                    friendly_message: Some(friendly_message),
+                })
+            }
+            GitHubConnectorOp::UpdatePages(pages_config) => {
+                if existing.is_none() {
+                    bail!(
+                        "GitHub Pages for {}/{} is no longer enabled. Re-run the plan to enable it instead.",
+                        owner,
+                        repo
+                    );
+                }
+
+                let mut friendly_message = format!("Updated GitHub Pages for {}/{}", owner, repo);
+                if let Some(warning) = apply_pages_update(&client, owner, repo, &pages_config).await? {
+                    friendly_message = format!("{}\n{}", friendly_message, warning);
+                }
+
+                Ok(OpExecResponse {
+                    outputs: None,
+                    friendly_message: Some(friendly_message),
+                })
+            }
+            GitHubConnectorOp::DeletePages => {
+                if existing.is_none() {
+                    return no_op(format!("GitHub Pages for {}/{} is already disabled", owner, repo));
+                }
+
+                match client.delete_pages(owner, repo).await {
+                    Ok(()) => Ok(OpExecResponse {
+                        outputs: None,
+                        friendly_message: Some(format!("Disabled GitHub Pages for {}/{}", owner, repo)),
+                    }),
+                    Err(e) => bail!("Failed to disable GitHub Pages for {}/{}: {:#?}", owner, repo, e),
+                }
+            }
+            _ => Err(invalid_op(
+                &GitHubResourceAddress::Pages {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                },
+                &op,
+            )),
+        }
+    }
+
+    async fn exec_runner_group_op(&self, org: &str, name: &str, op: GitHubConnectorOp) -> anyhow::Result<OpExecResponse> {
+        let client = self.client.read().await.clone();
+        let existing = client.get_runner_group(org, name).await?;
+
+        match op {
+            GitHubConnectorOp::CreateRunnerGroup(group_config) => {
+                // A retried create may find that GitHub already processed the first attempt.
+                if let Some(existing) = existing {
+                    let group_id = existing.id;
+                    if runner_group_resource(&client, org, existing).await? == group_config {
+                        return Ok(OpExecResponse {
+                            outputs: Some(HashMap::from([(String::from("id"), Some(group_id.to_string()))])),
+                            friendly_message: Some(format!(
+                                "Runner group {} in organization {} is already in place; nothing to do",
+                                name, org
+                            )),
+                        });
+                    }
+                    bail!(
+                        "Runner group {} in organization {} already exists with different settings. Re-run the plan to update it instead.",
+                        name,
+                        org
+                    );
+                }
+
+                let mut request = runner_group_request(name, &group_config);
+                if let RunnerGroupVisibility::Selected(repos) = &group_config.visibility {
+                    request.selected_repository_ids = Some(
+                        repository_ids(&client, org, repos)
+                            .await
+                            .context("Failed to look up the repositories for the runner group")?,
+                    );
+                }
+
+                match client.create_runner_group(org, &request).await {
+                    Ok(group) => Ok(OpExecResponse {
+                        outputs: Some(HashMap::from([(String::from("id"), Some(group.id.to_string()))])),
+                        friendly_message: Some(format!("Created runner group {} in organization {}", name, org)),
+                    }),
+                    Err(e) => bail!("Failed to create runner group {} in organization {}: {:#?}", name, org, e),
+                }
+            }
+            GitHubConnectorOp::UpdateRunnerGroup(group_config) => {
+                let Some(existing) = existing else {
+                    bail!(
+                        "Runner group {} in organization {} no longer exists. Re-run the plan to create it instead.",
+                        name,
+                        org
+                    );
+                };
+
+                // Look up the repositories first, so that a missing one leaves the group untouched.
+                let selected_repository_ids = match &group_config.visibility {
+                    RunnerGroupVisibility::Selected(repos) => Some(
+                        repository_ids(&client, org, repos)
+                            .await
+                            .context("Failed to look up the repositories for the runner group")?,
+                    ),
+                    _ => None,
+                };
+
+                let request = runner_group_request(name, &group_config);
+                if let Err(e) = client.update_runner_group(org, existing.id, &request).await {
+                    bail!("Failed to update runner group {} in organization {}: {:#?}", name, org, e);
+                }
+
+                if let Some(selected_repository_ids) = selected_repository_ids {
+                    let request = SetSelectedRepositoriesRequest { selected_repository_ids };
+                    if let Err(e) = client.set_runner_group_repositories(org, existing.id, &request).await {
+                        bail!(
+                            "Updated runner group {} in organization {}, but failed to set its repositories, \
+                             so the op only partly completed: {:#?}",
+                            name,
+                            org,
+                            e
+                        );
+                    }
+                }
+
+                Ok(OpExecResponse {
+                    outputs: Some(HashMap::from([(String::from("id"), Some(existing.id.to_string()))])),
+                    friendly_message: Some(format!("Updated runner group {} in organization {}", name, org)),
+                })
+            }
+            GitHubConnectorOp::DeleteRunnerGroup => {
+                let Some(existing) = existing else {
+                    return no_op(format!("Runner group {} in organization {} is already deleted", name, org));
+                };
+
+                if existing.default {
+                    bail!(
+                        "Runner group {} is the default group of organization {}, so it can't be deleted",
+                        name,
+                        org
+                    );
+                }
+
+                match client.delete_runner_group(org, existing.id).await {
+                    Ok(()) => Ok(OpExecResponse {
+                        outputs: None,
+                        friendly_message: Some(format!("Deleted runner group {} in organization {}", name, org)),
+                    }),
+                    Err(e) => bail!("Failed to delete runner group {} in organization {}: {:#?}", name, org, e),
+                }
+            }
+            _ => Err(invalid_op(
+                &GitHubResourceAddress::RunnerGroup {
+                    org: org.to_string(),
+                    name: name.to_string(),
+                },
+                &op,
+            )),
+        }
+    }
+
+    /// Build the GraphQL input shared by the create and update mutations. Push allowances are
+    /// given as node IDs, so users, teams (in the `owner` org) and apps are looked up by name first.
+    async fn branch_rule_input(
+        &self,
+        client: &Octocrab,
+        owner: &str,
+        rule_config: &BranchProtection,
+    ) -> anyhow::Result<serde_json::Value> {
+        let push_actor_ids = match &rule_config.restrictions {
+            Some(restrictions) => {
+                let allowances = ActorAllowances {
+                    users: restrictions.users.clone(),
+                    teams: restrictions.teams.clone(),
+                    apps: restrictions.apps.clone(),
+                };
+                resolve_actor_ids(client, owner, &allowances).await?
+            }
+            None => Vec::new(),
+        };
+
+        let reviews = rule_config.required_pull_request_reviews.as_ref();
+        let dismissal_actor_ids = match reviews.and_then(|reviews| reviews.dismissal_restrictions.as_ref()) {
+            Some(allowances) => resolve_actor_ids(client, owner, allowances).await?,
+            None => Vec::new(),
+        };
+        let bypass_actor_ids = match reviews.and_then(|reviews| reviews.bypass_pull_request_allowances.as_ref()) {
+            Some(allowances) => resolve_actor_ids(client, owner, allowances).await?,
+            None => Vec::new(),
+        };
+
+        let checks = rule_config.required_status_checks.as_ref();
+        if checks.is_some_and(|checks| !checks.checks.is_empty()) {
+            bail!("Branch rules don't support status checks pinned to an app yet; list them under contexts instead");
+        }
+
+        Ok(serde_json::json!({
+            "requiresStatusChecks": checks.is_some(),
+            "requiresStrictStatusChecks": checks.is_some_and(|checks| checks.strict),
+            "requiredStatusCheckContexts": checks.map(|checks| checks.contexts.clone()).unwrap_or_default(),
+            "isAdminEnforced": rule_config.enforce_admins,
+            "requiresApprovingReviews": reviews.is_some(),
+            "requiredApprovingReviewCount": reviews.map(|reviews| reviews.required_approving_review_count).unwrap_or_default(),
+            "dismissesStaleReviews": reviews.is_some_and(|reviews| reviews.dismiss_stale_reviews),
+            "requiresCodeOwnerReviews": reviews.is_some_and(|reviews| reviews.require_code_owner_reviews),
+            "requireLastPushApproval": reviews.is_some_and(|reviews| reviews.require_last_push_approval),
+            "restrictsReviewDismissals": reviews.is_some_and(|reviews| reviews.dismissal_restrictions.is_some()),
+            "reviewDismissalActorIds": dismissal_actor_ids,
+            "bypassPullRequestActorIds": bypass_actor_ids,
+            "restrictsPushes": rule_config.restrictions.is_some(),
+            "pushActorIds": push_actor_ids,
+            "requiresLinearHistory": rule_config.required_linear_history,
+            "requiresCommitSignatures": rule_config.required_signatures,
+            "allowsForcePushes": rule_config.allow_force_pushes,
+            "allowsDeletions": rule_config.allow_deletions,
+            "blocksCreations": rule_config.block_creations,
+            "requiresConversationResolution": rule_config.required_conversation_resolution,
+            "lockBranch": rule_config.lock_branch,
+            "lockAllowsFetchAndMerge": rule_config.allow_fork_syncing,
+        }))
+    }
 }
@@ -1,10 +1,27 @@
-use crate::{GitHubConnector, addr::GitHubResourceAddress, op::GitHubConnectorOp, resource};
+use crate::{
+    GitHubConnector,
+    addr::GitHubResourceAddress,
+    config::DeletionPolicy,
+    connector::list::is_wildcard_pattern,
+    github_ext::{
+        AppExt, BranchOpsExt, BranchRuleExt, ListExt, PagesExt, RepositoryOpsExt, RunnerGroupExt, UpdateRepositoryRequest,
+    },
+    op::GitHubConnectorOp,
+    resource,
+};
+use anyhow::bail;
 use autoschematic_core::{
     connector::{ConnectorOp, PlanResponseElement, ResourceAddress},
     connector_op,
     util::{RON, diff_ron_values},
 };
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+/// The topic added to repositories that were archived in place of a delete.
+pub const DELETED_TOPIC_MARKER: &str = "autoschematic-deleted";
 
 impl GitHubConnector {
     pub async fn do_plan(
@@ -13,103 +30,1204 @@ impl GitHubConnector {
         current: Option<Vec<u8>>,
         desired: Option<Vec<u8>>,
     ) -> anyhow::Result<Vec<PlanResponseElement>> {
-        let current = current.map(String::from_utf8);
-        let desired = desired.map(String::from_utf8);
-
         let addr = GitHubResourceAddress::from_path(addr)?;
         let mut res = Vec::new();
 
-        match addr {
+        // Fingerprinted so op_exec can detect changes made between plan and apply.
+        let planned_from = current.clone();
+
+        let current = current.map(String::from_utf8);
+        let desired = desired.map(String::from_utf8);
+
+        match addr.clone() {
             GitHubResourceAddress::Config => {}
             GitHubResourceAddress::Repository { owner, repo } => match (current, desired) {
                 (None, None) => {}
                 (None, Some(desired)) => {
                     let new_repo: resource::GitHubRepository = RON.from_str(&desired?)?;
 
-                    res.push(connector_op!(
-                        GitHubConnectorOp::CreateRepository(new_repo),
-                        format!("Create GitHub repository {}/{}", owner, repo)
-                    ));
-                }
-                (Some(_), None) => {
-                    res.push(connector_op!(
-                        GitHubConnectorOp::DeleteRepository,
-                        format!("Delete GitHub repository {}/{}", owner, repo)
-                    ));
+                    if let Some(moved_from) = new_repo.moved_from.clone() {
+                        let Some((from_owner, from_repo)) = moved_from.split_once('/') else {
+                            bail!(
+                                "Invalid moved_from repository {:?} for {}/{}: expected \"owner/repo\"",
+                                moved_from,
+                                owner,
+                                repo
+                            );
+                        };
+
+                        let from_addr = GitHubResourceAddress::Repository {
+                            owner: from_owner.to_string(),
+                            repo: from_repo.to_string(),
+                        };
+
+                        if let Some(previous) = self.do_get(&from_addr.to_path_buf()).await? {
+                            let old_repo: resource::GitHubRepository =
+                                RON.from_str(str::from_utf8(&previous.resource_definition)?)?;
+
+                            if from_owner == owner {
+                                res.push(connector_op!(
+                                    GitHubConnectorOp::RenameRepository(from_repo.to_string()),
+                                    format!("Rename GitHub repository {} to {}/{}", moved_from, owner, repo)
+                                ));
+                            } else {
+                                res.push(connector_op!(
+                                    GitHubConnectorOp::TransferRepository(from_owner.to_string(), from_repo.to_string()),
+                                    format!("Transfer GitHub repository {} to {}/{}", moved_from, owner, repo)
+                                ));
+                            }
+
+                            res.append(&mut self.plan_repository_update(&owner, &repo, old_repo, new_repo).await?);
+                            return Ok(res);
+                        }
+
+                        tracing::warn!(
+                            "Repository {}/{} declares moved_from {}, which doesn't exist; planning a create instead",
+                            owner,
+                            repo,
+                            moved_from
+                        );
+                    }
+
+                    self.check_custom_properties(&owner, &repo, &new_repo)?;
+
+                    let custom_properties = new_repo.custom_properties.clone();
+                    let topics = new_repo.topics.clone();
+
+                    let message = match (&new_repo.fork_of, &new_repo.template) {
+                        (Some(_), Some(_)) => {
+                            bail!("Repository {}/{} can't set both fork_of and template", owner, repo)
+                        }
+                        (Some(fork_of), None) => format!("Create GitHub repository {}/{} as a fork of {}", owner, repo, fork_of),
+                        (None, Some(template)) => format!(
+                            "Create GitHub repository {}/{} from template {}",
+                            owner, repo, template.repository
+                        ),
+                        (None, None) => format!("Create GitHub repository {}/{}", owner, repo),
+                    };
+
+                    res.push(connector_op!(GitHubConnectorOp::CreateRepository(new_repo), message));
+
+                    // Topics have their own endpoint, like on an update.
+                    if !topics.is_empty() {
+                        res.push(connector_op!(
+                            GitHubConnectorOp::SetTopics(topics.clone()),
+                            format!("Set topics on GitHub repository {}/{} to {:?}", owner, repo, topics)
+                        ));
+                    }
+
+                    // The create endpoints don't take custom properties, so they're set once the repository exists.
+                    if !custom_properties.is_empty() {
+                        let message = format!(
+                            "Set custom properties on GitHub repository {}/{}\n{}",
+                            owner,
+                            repo,
+                            diff_ron_values(&BTreeMap::<String, resource::CustomPropertyValue>::new(), &custom_properties)
+                                .unwrap_or_default()
+                        );
+                        let values = custom_properties
+                            .into_iter()
+                            .map(|(name, value)| (name, Some(value)))
+                            .collect();
+                        res.push(connector_op!(GitHubConnectorOp::SetCustomPropertyValues(values), message));
+                    }
                 }
-                (Some(current), Some(desired)) => {
-                    if current != desired {
-                        let mut old_repo: resource::GitHubRepository = RON.from_str(&current?)?;
-                        let mut new_repo: resource::GitHubRepository = RON.from_str(&desired?)?;
+                (Some(current), None) => {
+                    // If another repository.ron declares that it was moved from here, the rename or transfer
+                    // is planned at the new address and this repository must not be deleted.
+                    if let Some((new_owner, new_repo)) = self.find_moved_repository(&owner, &repo)? {
+                        tracing::info!(
+                            "Repository {}/{} is moving to {}/{}, not planning a delete",
+                            owner,
+                            repo,
+                            new_owner,
+                            new_repo
+                        );
+                    } else {
+                        let policy = self.config.read().await.deletion_policy_for(&owner, &repo);
 
-                        if old_repo.collaborators != new_repo.collaborators {
-                            for (k, v) in &new_repo.collaborators {
-                                if !old_repo.collaborators.contains_key(k) {
-                                    res.push(connector_op!(
-                                        GitHubConnectorOp::AddCollaborator(k.clone(), v.clone()),
-                                        format!("Add Collaborator {:?} to repo {}/{} with role {:?}", k, owner, repo, v)
-                                    ));
-                                } else if old_repo.collaborators.get(k) != Some(v) {
+                        match policy {
+                            DeletionPolicy::Forbid => bail!(
+                                "Refusing to delete GitHub repository {}/{}: deletion_policy is Forbid. \
+                                 Change the policy in github/config.ron to Archive or Allow to proceed.",
+                                owner,
+                                repo
+                            ),
+                            DeletionPolicy::Archive => {
+                                let old_repo: resource::GitHubRepository = RON.from_str(&current?)?;
+
+                                if !old_repo.topics.iter().any(|t| t == DELETED_TOPIC_MARKER) {
+                                    let mut topics = old_repo.topics.clone();
+                                    topics.push(DELETED_TOPIC_MARKER.to_string());
                                     res.push(connector_op!(
-                                        GitHubConnectorOp::UpdateCollaborator(k.clone(), v.clone()),
-                                        format!("Update Collaborator {:?} on repo {}/{} to role {:?}", k, owner, repo, v)
+                                        GitHubConnectorOp::SetTopics(topics),
+                                        format!(
+                                            "Mark GitHub repository {}/{} as deleted with topic {}",
+                                            owner, repo, DELETED_TOPIC_MARKER
+                                        )
                                     ));
                                 }
-                            }
-                            for (k, _) in &old_repo.collaborators {
-                                if !new_repo.collaborators.contains_key(k) {
+
+                                if !old_repo.archived {
                                     res.push(connector_op!(
-                                        GitHubConnectorOp::RemoveCollaborator(k.clone()),
-                                        format!("Remove Collaborator {:?} from repo {}/{}", k, owner, repo)
+                                        GitHubConnectorOp::ArchiveRepository,
+                                        format!(
+                                            "Archive GitHub repository {}/{} instead of deleting it (deletion_policy is Archive)",
+                                            owner, repo
+                                        )
                                     ));
                                 }
                             }
+                            DeletionPolicy::Allow => {
+                                let client = self.client.read().await.clone();
+                                let at_risk = match client.get_open_counts(&owner, &repo).await {
+                                    Ok(counts) => format!(
+                                        "{} open pull requests and {} open issues will be lost",
+                                        counts.pull_requests, counts.issues
+                                    ),
+                                    Err(e) => {
+                                        tracing::warn!("Failed to count open pull requests and issues: {:#}", e);
+                                        String::from("open pull request and issue counts unavailable")
+                                    }
+                                };
+
+                                res.push(connector_op!(
+                                    GitHubConnectorOp::DeleteRepository,
+                                    format!("DESTRUCTIVE: Delete GitHub repository {}/{} ({})", owner, repo, at_risk)
+                                ));
+                            }
                         }
-                        
-                        // Now that we've computed the collaborator updates manually, exclude them from the diff.
-                        old_repo.collaborators = HashMap::new();
-                        new_repo.collaborators = HashMap::new();
-
-                        // Only update repository if other fields changed
-                        if old_repo != new_repo {
-                            let diff = diff_ron_values(&old_repo, &new_repo).unwrap_or_default();
-                            res.push(connector_op!(
-                                GitHubConnectorOp::UpdateRepository(new_repo),
-                                format!("Update GitHub repository {}/{}\n{}", owner, repo, diff)
-                            ));
-                        }
+                    }
+                }
+                (Some(current), Some(desired)) => {
+                    if current != desired {
+                        let old_repo: resource::GitHubRepository = RON.from_str(&current?)?;
+                        let new_repo: resource::GitHubRepository = RON.from_str(&desired?)?;
+
+                        res.append(&mut self.plan_repository_update(&owner, &repo, old_repo, new_repo).await?);
                     }
                 }
             },
-            GitHubResourceAddress::BranchProtection { owner, repo, branch } => match (current, desired) {
-                (None, None) => {}
+            GitHubResourceAddress::BranchProtection { owner, repo, branch } => {
+                let current = current.transpose()?;
+                let desired = desired.transpose()?;
+
+                if current != desired && self.repository_archive_pending(&owner, &repo).await? {
+                    // The repository plan carries these changes so they're ordered around the archive state change.
+                    tracing::info!(
+                        "Deferring branch protection changes for {}/{} branch {} to the repository's archive state change",
+                        owner,
+                        repo,
+                        branch
+                    );
+                } else {
+                    let current = current.as_deref().map(|current| RON.from_str(current)).transpose()?;
+                    let desired = self.parse_desired_protection(desired.as_deref()).await?;
+
+                    for (op, message) in plan_branch_protection(&owner, &repo, &branch, current, desired) {
+                        res.push(connector_op!(op, message));
+                    }
+                }
+            }
+            GitHubResourceAddress::Branch { owner, repo, branch } => match (current, desired) {
                 (None, Some(desired)) => {
-                    let new_protection: resource::BranchProtection = RON.from_str(&desired?)?;
+                    let new_branch: resource::Branch = RON.from_str(&desired?)?;
 
-                    res.push(connector_op!(
-                        GitHubConnectorOp::CreateBranchProtection(new_protection),
-                        format!("Create branch protection for {}/{} branch {}", owner, repo, branch)
-                    ));
+                    let message = format!("Create branch {} in {}/{} from {}", branch, owner, repo, new_branch.source);
+                    res.push(connector_op!(GitHubConnectorOp::CreateBranch(new_branch), message));
                 }
                 (Some(_), None) => {
+                    let client = self.client.read().await.clone();
+                    let open_pulls = client.count_open_pulls_into(&owner, &repo, &branch).await?;
+                    if open_pulls > 0 {
+                        bail!(
+                            "Branch {} of {}/{} has {} open pull request(s) targeting it, so it won't be deleted. \
+                             Merge, close or retarget them first.",
+                            branch,
+                            owner,
+                            repo,
+                            open_pulls
+                        );
+                    }
+
                     res.push(connector_op!(
-                        GitHubConnectorOp::DeleteBranchProtection,
-                        format!("Delete branch protection for {}/{} branch {}", owner, repo, branch)
+                        GitHubConnectorOp::DeleteBranch,
+                        format!("DESTRUCTIVE: Delete branch {} of {}/{}", branch, owner, repo)
                     ));
                 }
-                (Some(current), Some(desired)) => {
-                    if current != desired {
-                        let old_protection: resource::BranchProtection = RON.from_str(&current?)?;
-                        let new_protection: resource::BranchProtection = RON.from_str(&desired?)?;
-                        let diff = diff_ron_values(&old_protection, &new_protection).unwrap_or_default();
+                // The source is only used to create the branch, so there's nothing to update.
+                _ => {}
+            },
+            GitHubResourceAddress::CustomProperties { org } => {
+                let current = current.transpose()?.map(|current| RON.from_str(&current)).transpose()?;
+                let desired = desired.transpose()?.map(|desired| RON.from_str(&desired)).transpose()?;
 
-                        res.push(connector_op!(
-                            GitHubConnectorOp::UpdateBranchProtection(new_protection),
-                            format!("Update branch protection for {}/{} branch {}\n{}", owner, repo, branch, diff)
-                        ));
+                for (op, message) in plan_custom_properties(&org, current, desired) {
+                    res.push(connector_op!(op, message));
+                }
+            }
+            GitHubResourceAddress::ActionsPermissions { owner, repo } => {
+                let mut current: Option<resource::ActionsPermissions> =
+                    current.transpose()?.map(|current| RON.from_str(&current)).transpose()?;
+                let mut desired: Option<resource::ActionsPermissions> =
+                    desired.transpose()?.map(|desired| RON.from_str(&desired)).transpose()?;
+                current.iter_mut().chain(desired.iter_mut()).for_each(|p| p.clear_unused());
+
+                let subject = format!("GitHub Actions permissions for {}/{}", owner, repo);
+                for (op, message) in plan_settings(&subject, current, desired, GitHubConnectorOp::SetActionsPermissions) {
+                    res.push(connector_op!(op, message));
+                }
+            }
+            GitHubResourceAddress::OrgActionsPermissions { org } => {
+                let mut current: Option<resource::OrgActionsPermissions> =
+                    current.transpose()?.map(|current| RON.from_str(&current)).transpose()?;
+                let mut desired: Option<resource::OrgActionsPermissions> =
+                    desired.transpose()?.map(|desired| RON.from_str(&desired)).transpose()?;
+                current.iter_mut().chain(desired.iter_mut()).for_each(|p| p.clear_unused());
+
+                let subject = format!("GitHub Actions permissions for organization {}", org);
+                for (op, message) in plan_settings(&subject, current, desired, GitHubConnectorOp::SetOrgActionsPermissions) {
+                    res.push(connector_op!(op, message));
+                }
+            }
+            GitHubResourceAddress::ActionsSettings { owner, repo } => {
+                let mut current: Option<resource::ActionsSettings> =
+                    current.transpose()?.map(|current| RON.from_str(&current)).transpose()?;
+                let mut desired: Option<resource::ActionsSettings> =
+                    desired.transpose()?.map(|desired| RON.from_str(&desired)).transpose()?;
+                current.iter_mut().chain(desired.iter_mut()).for_each(|s| s.clear_unused());
+
+                for (op, message) in plan_actions_settings(&owner, &repo, current, desired)? {
+                    res.push(connector_op!(op, message));
+                }
+            }
+            GitHubResourceAddress::Pages { owner, repo } => {
+                let mut current: Option<resource::Pages> =
+                    current.transpose()?.map(|current| RON.from_str(&current)).transpose()?;
+                let desired: Option<resource::Pages> = desired.transpose()?.map(|desired| RON.from_str(&desired)).transpose()?;
+
+                // Leaving the visibility out leaves it as it is.
+                if desired.as_ref().is_some_and(|desired| desired.visibility.is_none()) {
+                    current.iter_mut().for_each(|current| current.visibility = None);
+                }
+
+                let warning = match &desired {
+                    Some(desired) => self.pages_domain_warning(&owner, &repo, current.as_ref(), desired).await,
+                    None => None,
+                };
+
+                for (op, mut message) in plan_pages(&owner, &repo, current, desired) {
+                    if let Some(warning) = &warning {
+                        message = format!("{}\n{}", message, warning);
                     }
+                    res.push(connector_op!(op, message));
                 }
-            },
+            }
+            GitHubResourceAddress::CodeScanning { owner, repo } => {
+                let mut current: Option<resource::CodeScanningDefaultSetup> =
+                    current.transpose()?.map(|current| RON.from_str(&current)).transpose()?;
+                let mut desired: Option<resource::CodeScanningDefaultSetup> =
+                    desired.transpose()?.map(|desired| RON.from_str(&desired)).transpose()?;
+                current.iter_mut().chain(desired.iter_mut()).for_each(|s| s.clear_unused());
+
+                // With no languages given, GitHub picks them, so whichever it picked are fine.
+                if desired.as_ref().is_some_and(|desired| desired.languages.is_empty()) {
+                    current.iter_mut().for_each(|current| current.languages.clear());
+                }
+
+                let subject = format!("code scanning default setup for {}/{}", owner, repo);
+                for (op, message) in plan_settings(&subject, current, desired, GitHubConnectorOp::SetCodeScanningDefaultSetup) {
+                    res.push(connector_op!(op, message));
+                }
+            }
+            GitHubResourceAddress::InteractionLimits { owner, repo } => {
+                let current = current.transpose()?.map(|current| RON.from_str(&current)).transpose()?;
+                let desired = desired.transpose()?.map(|desired| RON.from_str(&desired)).transpose()?;
+
+                let subject = format!("{}/{}", owner, repo);
+                for (op, message) in plan_interaction_limits(&subject, current, desired) {
+                    res.push(connector_op!(op, message));
+                }
+            }
+            GitHubResourceAddress::OrgInteractionLimits { org } => {
+                let current = current.transpose()?.map(|current| RON.from_str(&current)).transpose()?;
+                let desired = desired.transpose()?.map(|desired| RON.from_str(&desired)).transpose()?;
+
+                let subject = format!("organization {}", org);
+                for (op, message) in plan_interaction_limits(&subject, current, desired) {
+                    res.push(connector_op!(op, message));
+                }
+            }
+            GitHubResourceAddress::RunnerGroup { org, name } => {
+                let mut current: Option<resource::RunnerGroup> =
+                    current.transpose()?.map(|current| RON.from_str(&current)).transpose()?;
+                let mut desired: Option<resource::RunnerGroup> =
+                    desired.transpose()?.map(|desired| RON.from_str(&desired)).transpose()?;
+                current.iter_mut().chain(desired.iter_mut()).for_each(|g| g.clear_unused());
+
+                // Every organization keeps its default group, which is where deleted groups' runners go.
+                if current.is_some() && desired.is_none() {
+                    let client = self.client.read().await.clone();
+                    if let Ok(Some(group)) = client.get_runner_group(&org, &name).await
+                        && group.default
+                    {
+                        bail!(
+                            "Runner group {} is the default group of organization {}, so it can't be deleted. \
+                             Restore its runner_groups/{}.ron.",
+                            name,
+                            org,
+                            name
+                        );
+                    }
+                }
+
+                for (op, message) in plan_runner_group(&org, &name, current, desired) {
+                    res.push(connector_op!(op, message));
+                }
+            }
+            GitHubResourceAddress::BranchRule { owner, repo, pattern } => {
+                let current = current.transpose()?;
+                let desired = desired.transpose()?;
+
+                if current != desired && self.repository_archive_pending(&owner, &repo).await? {
+                    tracing::info!(
+                        "Deferring branch rule changes for {}/{} pattern {} to the repository's archive state change",
+                        owner,
+                        repo,
+                        pattern
+                    );
+                } else {
+                    for (op, message) in plan_branch_rule(&owner, &repo, &pattern, current.as_deref(), desired.as_deref())? {
+                        res.push(connector_op!(op, message));
+                    }
+                }
+            }
+        }
+
+        self.guard_plan(&addr, planned_from.as_deref(), &mut res).await?;
+
+        Ok(res)
+    }
+}
+
+/// Build an update containing only the settings that differ between `old` and `new`, so that
+/// unrelated settings (and settings the token may not be allowed to write) are left untouched.
+fn repository_patch(old: &resource::GitHubRepository, new: &resource::GitHubRepository) -> UpdateRepositoryRequest {
+    fn changed<T: PartialEq + Clone>(old: &T, new: &T) -> Option<T> {
+        if old != new { Some(new.clone()) } else { None }
+    }
+
+    UpdateRepositoryRequest {
+        // An empty string clears the description or homepage; None would leave it unchanged.
+        description: changed(&old.description, &new.description).map(Option::unwrap_or_default),
+        homepage: changed(&old.homepage, &new.homepage).map(Option::unwrap_or_default),
+        private: changed(&old.private, &new.private),
+        has_issues: changed(&old.has_issues, &new.has_issues),
+        has_projects: changed(&old.has_projects, &new.has_projects),
+        has_wiki: changed(&old.has_wiki, &new.has_wiki),
+        allow_squash_merge: changed(&old.allow_squash_merge, &new.allow_squash_merge),
+        allow_merge_commit: changed(&old.allow_merge_commit, &new.allow_merge_commit),
+        allow_rebase_merge: changed(&old.allow_rebase_merge, &new.allow_rebase_merge),
+        allow_auto_merge: changed(&old.allow_auto_merge, &new.allow_auto_merge),
+        delete_branch_on_merge: changed(&old.delete_branch_on_merge, &new.delete_branch_on_merge),
+        default_branch: changed(&old.default_branch, &new.default_branch),
+        is_template: changed(&old.is_template, &new.is_template),
+        // Renames and archive state are planned as their own ops.
+        name: None,
+        archived: None,
+    }
+}
+
+/// Plan the changes between two states of a branch's protection.
+fn plan_branch_protection(
+    owner: &str,
+    repo: &str,
+    branch: &str,
+    current: Option<resource::BranchProtection>,
+    desired: Option<resource::BranchProtection>,
+) -> Vec<(GitHubConnectorOp, String)> {
+    let mut res = Vec::new();
+
+    match (current, desired) {
+        (None, None) => {}
+        (None, Some(new_protection)) => {
+            res.push((
+                GitHubConnectorOp::CreateBranchProtection(new_protection),
+                format!("Create branch protection for {}/{} branch {}", owner, repo, branch),
+            ));
+        }
+        (Some(_), None) => {
+            res.push((
+                GitHubConnectorOp::DeleteBranchProtection,
+                format!("Delete branch protection for {}/{} branch {}", owner, repo, branch),
+            ));
+        }
+        (Some(old_protection), Some(new_protection)) => {
+            if old_protection != new_protection {
+                let diff = diff_ron_values(&old_protection, &new_protection).unwrap_or_default();
+
+                res.push((
+                    GitHubConnectorOp::UpdateBranchProtection(new_protection),
+                    format!("Update branch protection for {}/{} branch {}\n{}", owner, repo, branch, diff),
+                ));
+            }
+        }
+    }
+
+    res
+}
+
+/// Plan the changes between two states of a pattern-based branch protection rule.
+fn plan_branch_rule(
+    owner: &str,
+    repo: &str,
+    pattern: &str,
+    current: Option<&str>,
+    desired: Option<&str>,
+) -> anyhow::Result<Vec<(GitHubConnectorOp, String)>> {
+    let mut res = Vec::new();
+
+    match (current, desired) {
+        (None, None) => {}
+        (None, Some(desired)) => {
+            let new_rule: resource::BranchProtection = RON.from_str(desired)?;
+
+            res.push((
+                GitHubConnectorOp::CreateBranchRule(new_rule),
+                format!("Create branch rule for {}/{} pattern {}", owner, repo, pattern),
+            ));
+        }
+        (Some(_), None) => {
+            res.push((
+                GitHubConnectorOp::DeleteBranchRule,
+                format!("Delete branch rule for {}/{} pattern {}", owner, repo, pattern),
+            ));
+        }
+        (Some(current), Some(desired)) => {
+            if current != desired {
+                let old_rule: resource::BranchProtection = RON.from_str(current)?;
+                let new_rule: resource::BranchProtection = RON.from_str(desired)?;
+                let diff = diff_ron_values(&old_rule, &new_rule).unwrap_or_default();
+
+                res.push((
+                    GitHubConnectorOp::UpdateBranchRule(new_rule),
+                    format!("Update branch rule for {}/{} pattern {}\n{}", owner, repo, pattern, diff),
+                ));
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+/// Plan the changes between two states of a repository's GitHub Pages site.
+fn plan_pages(
+    owner: &str,
+    repo: &str,
+    current: Option<resource::Pages>,
+    desired: Option<resource::Pages>,
+) -> Vec<(GitHubConnectorOp, String)> {
+    match (current, desired) {
+        (None, None) => Vec::new(),
+        (None, Some(desired)) => vec![(
+            GitHubConnectorOp::CreatePages(desired),
+            format!("Enable GitHub Pages for {}/{}", owner, repo),
+        )],
+        (Some(_), None) => vec![(
+            GitHubConnectorOp::DeletePages,
+            format!(
+                "DESTRUCTIVE: Disable GitHub Pages for {}/{}, taking its site down",
+                owner, repo
+            ),
+        )],
+        (Some(current), Some(desired)) if current == desired => Vec::new(),
+        (Some(current), Some(desired)) => {
+            let diff = diff_ron_values(&current, &desired).unwrap_or_default();
+            vec![(
+                GitHubConnectorOp::UpdatePages(desired),
+                format!("Update GitHub Pages for {}/{}\n{}", owner, repo, diff),
+            )]
+        }
+    }
+}
+
+/// Plan the changes to an interaction limit. Only the limit is compared, since the expiry can't be read back.
+fn plan_interaction_limits(
+    subject: &str,
+    current: Option<resource::InteractionLimits>,
+    desired: Option<resource::InteractionLimits>,
+) -> Vec<(GitHubConnectorOp, String)> {
+    match (current, desired) {
+        (None, None) => Vec::new(),
+        // An expired limit reads back as absent, so this is usually a limit that ran out.
+        (None, Some(desired)) => {
+            let message = format!(
+                "Limit interactions on {} to {} for {}\nNo limit is in place: either it was never applied, or the limit \
+                 expired, will re-apply",
+                subject,
+                desired.limit.to_string(),
+                desired.expiry.to_string()
+            );
+            vec![(GitHubConnectorOp::SetInteractionLimits(desired), message)]
+        }
+        (Some(_), None) => vec![(
+            GitHubConnectorOp::RemoveInteractionLimits,
+            format!("Remove the interaction limit on {}", subject),
+        )],
+        (Some(current), Some(desired)) if current.limit == desired.limit => Vec::new(),
+        (Some(current), Some(desired)) => {
+            let message = format!(
+                "Change the interaction limit on {} from {} to {}, for {} from now",
+                subject,
+                current.limit.to_string(),
+                desired.limit.to_string(),
+                desired.expiry.to_string()
+            );
+            vec![(GitHubConnectorOp::SetInteractionLimits(desired), message)]
+        }
+    }
+}
+
+/// Plan the changes between two states of an organization's runner group.
+fn plan_runner_group(
+    org: &str,
+    name: &str,
+    current: Option<resource::RunnerGroup>,
+    desired: Option<resource::RunnerGroup>,
+) -> Vec<(GitHubConnectorOp, String)> {
+    match (current, desired) {
+        (None, None) => Vec::new(),
+        (None, Some(desired)) => {
+            let message = format!(
+                "Create runner group {} in organization {} for {} repositories",
+                name,
+                org,
+                desired.visibility.to_string()
+            );
+            vec![(GitHubConnectorOp::CreateRunnerGroup(desired), message)]
+        }
+        (Some(_), None) => vec![(
+            GitHubConnectorOp::DeleteRunnerGroup,
+            format!(
+                "DESTRUCTIVE: Delete runner group {} in organization {}. Its runners move to the default group.",
+                name, org
+            ),
+        )],
+        (Some(current), Some(desired)) if current == desired => Vec::new(),
+        (Some(current), Some(desired)) => {
+            let diff = diff_ron_values(&current, &desired).unwrap_or_default();
+            vec![(
+                GitHubConnectorOp::UpdateRunnerGroup(desired),
+                format!("Update runner group {} in organization {}\n{}", name, org, diff),
+            )]
+        }
+    }
+}
+
+/// Plan the changes to settings that always exist, so they can be changed but not created or deleted.
+/// Removing the file leaves the settings as they are.
+fn plan_settings<T: PartialEq + serde::Serialize>(
+    subject: &str,
+    current: Option<T>,
+    desired: Option<T>,
+    set_op: impl FnOnce(T) -> GitHubConnectorOp,
+) -> Vec<(GitHubConnectorOp, String)> {
+    let Some(desired) = desired else {
+        if current.is_some() {
+            tracing::info!("{} can't be deleted; leaving them as they are", subject);
+        }
+        return Vec::new();
+    };
+
+    let message = match &current {
+        Some(current) if *current == desired => return Vec::new(),
+        Some(current) => format!(
+            "Update {}\n{}",
+            subject,
+            diff_ron_values(current, &desired).unwrap_or_default()
+        ),
+        None => format!("Set {}", subject),
+    };
+
+    vec![(set_op(desired), message)]
+}
+
+/// Plan the changes to a repository's Actions settings. Each setting has its own endpoint, so only the
+/// ones that changed are set.
+fn plan_actions_settings(
+    owner: &str,
+    repo: &str,
+    current: Option<resource::ActionsSettings>,
+    desired: Option<resource::ActionsSettings>,
+) -> anyhow::Result<Vec<(GitHubConnectorOp, String)>> {
+    let Some(desired) = desired else {
+        if current.is_some() {
+            tracing::info!(
+                "GitHub Actions settings for {}/{} can't be deleted; leaving them as they are",
+                owner,
+                repo
+            );
+        }
+        return Ok(Vec::new());
+    };
+
+    let claim = &desired.oidc_subject_claim;
+    if !claim.use_default && claim.include_claim_keys.is_empty() {
+        bail!(
+            "The OIDC subject claim for {}/{} needs include_claim_keys unless use_default is set",
+            owner,
+            repo
+        );
+    }
+
+    let mut res = Vec::new();
+
+    let old_days = current.as_ref().map(|current| current.artifact_and_log_retention_days);
+    if old_days != Some(desired.artifact_and_log_retention_days) {
+        res.push((
+            GitHubConnectorOp::SetArtifactRetention(desired.artifact_and_log_retention_days),
+            format!(
+                "Keep workflow artifacts and logs for {}/{} for {} days",
+                owner, repo, desired.artifact_and_log_retention_days
+            ),
+        ));
+    }
+
+    // Leaving the fork pull request approval policy out leaves it as it is.
+    let old_fork_pr_approval = current.as_ref().and_then(|current| current.fork_pr_approval.as_ref());
+    if let Some(fork_pr_approval) = desired
+        .fork_pr_approval
+        .as_ref()
+        .filter(|approval| old_fork_pr_approval != Some(*approval))
+    {
+        res.push((
+            GitHubConnectorOp::SetForkPrApproval(fork_pr_approval.clone()),
+            format!(
+                "Set the fork pull request approval policy for {}/{} to {}",
+                owner,
+                repo,
+                fork_pr_approval.to_string()
+            ),
+        ));
+    }
+
+    // Leaving the access level out leaves it as it is.
+    let old_access_level = current.as_ref().and_then(|current| current.access_level.as_ref());
+    if let Some(access_level) = desired.access_level.as_ref().filter(|level| old_access_level != Some(*level)) {
+        res.push((
+            GitHubConnectorOp::SetActionsAccessLevel(access_level.clone()),
+            format!(
+                "Set the Actions access level for {}/{} to {}",
+                owner,
+                repo,
+                access_level.to_string()
+            ),
+        ));
+    }
+
+    match current.as_ref().map(|current| &current.oidc_subject_claim) {
+        Some(old_claim) if old_claim == claim => {}
+        old_claim => {
+            let mut message = format!("Set the OIDC subject claim template for {}/{}", owner, repo);
+            if let Some(old_claim) = old_claim {
+                message.push_str(&format!("\n{}", diff_ron_values(old_claim, claim).unwrap_or_default()));
+            }
+            message.push_str(
+                "\nWARNING: this changes the sub claim of every OIDC token issued to this repository's workflows. \
+                 Cloud trust policies that match the old format will reject its tokens.",
+            );
+
+            res.push((GitHubConnectorOp::SetOidcSubjectClaim(claim.clone()), message));
+        }
+    }
+
+    Ok(res)
+}
+
+/// Plan the changes between two states of an organization's custom property definitions.
+fn plan_custom_properties(
+    org: &str,
+    current: Option<resource::CustomProperties>,
+    desired: Option<resource::CustomProperties>,
+) -> Vec<(GitHubConnectorOp, String)> {
+    let old_properties = current.map(|current| current.properties).unwrap_or_default();
+    let new_properties = desired.map(|desired| desired.properties).unwrap_or_default();
+
+    let mut res = Vec::new();
+
+    let changed: BTreeMap<String, resource::CustomProperty> = new_properties
+        .iter()
+        .filter(|(name, property)| old_properties.get(*name) != Some(*property))
+        .map(|(name, property)| (name.clone(), property.clone()))
+        .collect();
+
+    if !changed.is_empty() {
+        let old_changed: BTreeMap<String, resource::CustomProperty> = old_properties
+            .iter()
+            .filter(|(name, _)| changed.contains_key(*name))
+            .map(|(name, property)| (name.clone(), property.clone()))
+            .collect();
+        let diff = diff_ron_values(&old_changed, &changed).unwrap_or_default();
+
+        res.push((
+            GitHubConnectorOp::UpsertCustomProperties(changed),
+            format!("Set custom property definitions in organization {}\n{}", org, diff),
+        ));
+    }
+
+    for name in old_properties.keys() {
+        if !new_properties.contains_key(name) {
+            res.push((
+                GitHubConnectorOp::DeleteCustomProperty(name.clone()),
+                format!(
+                    "DESTRUCTIVE: Delete custom property {} from organization {}, removing its value from every repository",
+                    name, org
+                ),
+            ));
+        }
+    }
+
+    res
+}
+
+impl GitHubConnector {
+    /// Check a repository's custom property values against its organization's custom_properties.ron
+    /// in the workspace. Without one, there's nothing to check against.
+    fn check_custom_properties(&self, owner: &str, repo: &str, repo_config: &resource::GitHubRepository) -> anyhow::Result<()> {
+        let schema_addr = GitHubResourceAddress::CustomProperties { org: owner.to_string() };
+        let Some(schema) = self.read_workspace_file(&schema_addr)? else {
+            return Ok(());
+        };
+
+        let schema: resource::CustomProperties = RON.from_str(&schema)?;
+        let problems = schema.check_values(&repo_config.custom_properties);
+        if !problems.is_empty() {
+            bail!(
+                "Repository {}/{} has invalid custom properties:\n  {}",
+                owner,
+                repo,
+                problems.join("\n  ")
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the app slugs in a branch protection's pinned status checks to app IDs, since
+    /// GitHub only reports the ID.
+    async fn resolve_check_apps(&self, protection: &mut resource::BranchProtection) -> anyhow::Result<()> {
+        let Some(required_status_checks) = &mut protection.required_status_checks else {
+            return Ok(());
+        };
+
+        let client = self.client.read().await.clone();
+        for check in &mut required_status_checks.checks {
+            let Some(app_slug) = check.app.take() else {
+                continue;
+            };
+
+            let app = match client.get_app(&app_slug).await {
+                Ok(app) => app,
+                Err(e) => bail!("Failed to look up app {} for status check {}: {}", app_slug, check.context, e),
+            };
+
+            if check.app_id.is_some_and(|app_id| app_id != app.id) {
+                bail!(
+                    "Status check {} names app {} (ID {}) but also app_id {:?}",
+                    check.context,
+                    app_slug,
+                    app.id,
+                    check.app_id
+                );
+            }
+            check.app_id = Some(app.id);
+        }
+
+        Ok(())
+    }
+
+    /// Parse a desired branch protection, resolving app slugs so it compares equal to what do_get returns.
+    async fn parse_desired_protection(&self, desired: Option<&str>) -> anyhow::Result<Option<resource::BranchProtection>> {
+        let Some(desired) = desired else {
+            return Ok(None);
+        };
+
+        let mut protection: resource::BranchProtection = RON.from_str(desired)?;
+        self.resolve_check_apps(&mut protection).await?;
+        Ok(Some(protection))
+    }
+
+    /// Warn when GitHub is likely to hold back a Pages custom domain or HTTPS enforcement because the
+    /// domain's DNS isn't verified or its certificate isn't issued yet.
+    async fn pages_domain_warning(
+        &self,
+        owner: &str,
+        repo: &str,
+        current: Option<&resource::Pages>,
+        desired: &resource::Pages,
+    ) -> Option<String> {
+        let domain = desired.custom_domain.as_ref()?;
+
+        let domain_changes = current.and_then(|current| current.custom_domain.as_ref()) != Some(domain);
+        if !domain_changes {
+            if !desired.https_enforced || current.is_some_and(|current| current.https_enforced) {
+                return None;
+            }
+
+            let client = self.client.read().await.clone();
+            let certificate_approved = match client.get_pages(owner, repo).await {
+                Ok(pages) => pages
+                    .https_certificate
+                    .is_some_and(|certificate| certificate.state == "approved"),
+                Err(_) => false,
+            };
+            if certificate_approved {
+                return None;
+            }
+        }
+
+        Some(format!(
+            "WARNING: GitHub may not accept the custom domain {}{} until its DNS points at GitHub Pages and a certificate \
+             is issued. If so, the apply reports it as a warning rather than failing, and the next plan tries again.",
+            domain,
+            if desired.https_enforced { " with HTTPS enforced" } else { "" }
+        ))
+    }
+
+    /// Whether the repository's archive state is about to change, or it is staying archived.
+    /// Either way, changes at sibling addresses can't be applied on their own.
+    async fn repository_archive_pending(&self, owner: &str, repo: &str) -> anyhow::Result<bool> {
+        let client = self.client.read().await.clone();
+        let Ok(github_repo) = client.repos(owner, repo).get().await else {
+            return Ok(false);
+        };
+        let live_archived = github_repo.archived.unwrap_or(false);
+
+        let repo_addr = GitHubResourceAddress::Repository {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        };
+        let desired_archived = match self.read_workspace_file(&repo_addr)? {
+            Some(desired) => RON.from_str::<resource::GitHubRepository>(&desired)?.archived,
+            None => live_archived,
+        };
+
+        if live_archived && desired_archived {
+            bail!(
+                "Repository {}/{} is archived, so GitHub will reject changes to it. Set archived: false in its repository.ron first.",
+                owner,
+                repo
+            );
+        }
+
+        Ok(live_archived != desired_archived)
+    }
+
+    /// Plan the branch protection changes for every branch of a repository, for inclusion in the
+    /// repository's own plan. Covers branches with a protection.ron in the workspace and branch rules
+    /// in the workspace, plus whatever is currently protected, addressed the same way list does.
+    async fn plan_sibling_branch_protections(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> anyhow::Result<Vec<(GitHubConnectorOp, String)>> {
+        let mut branches = self.workspace_protected_branches(owner, repo)?;
+
+        let client = self.client.read().await.clone();
+        let rules = client.list_branch_rules(owner, repo).await.ok();
+
+        let mut branch_names = Vec::new();
+        if let Ok(branch_page) = client.list_repo_branches(owner, repo).await {
+            for branch in client.all_pages(branch_page).await? {
+                // A branch that's only protected through a wildcard rule has no protection of its own.
+                let has_own_protection = match &rules {
+                    Some(rules) => rules.iter().any(|rule| rule.pattern == branch.name),
+                    None => branch.protected,
+                };
+
+                if has_own_protection && !branches.contains(&branch.name) {
+                    branches.push(branch.name.clone());
+                }
+                branch_names.push(branch.name);
+            }
+        }
+
+        let mut res = Vec::new();
+        for branch in branches {
+            let addr = GitHubResourceAddress::BranchProtection {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+                branch: branch.clone(),
+            };
+
+            let current = self
+                .do_get(&addr.to_path_buf())
+                .await?
+                .map(|current| String::from_utf8(current.resource_definition))
+                .transpose()?;
+            let current = current.as_deref().map(|current| RON.from_str(current)).transpose()?;
+            let desired = self
+                .parse_desired_protection(self.read_workspace_file(&addr)?.as_deref())
+                .await?;
+
+            for (op, message) in plan_branch_protection(owner, repo, &branch, current, desired) {
+                res.push((GitHubConnectorOp::ForBranch(branch.clone(), Box::new(op)), message));
+            }
+        }
+
+        // Rules naming a branch that doesn't exist (yet) are branch rules too.
+        let mut patterns = self.workspace_branch_rules(owner, repo)?;
+        for rule in rules.unwrap_or_default() {
+            let is_branch_rule = is_wildcard_pattern(&rule.pattern) || !branch_names.contains(&rule.pattern);
+            if is_branch_rule && !patterns.contains(&rule.pattern) {
+                patterns.push(rule.pattern);
+            }
+        }
+
+        for pattern in patterns {
+            let addr = GitHubResourceAddress::BranchRule {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+                pattern: pattern.clone(),
+            };
+
+            let current = self
+                .do_get(&addr.to_path_buf())
+                .await?
+                .map(|current| String::from_utf8(current.resource_definition))
+                .transpose()?;
+            let desired = self.read_workspace_file(&addr)?;
+
+            for (op, message) in plan_branch_rule(owner, repo, &pattern, current.as_deref(), desired.as_deref())? {
+                res.push((GitHubConnectorOp::ForBranchRule(pattern.clone(), Box::new(op)), message));
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// The files under branches/{from}/ in the workspace, each paired with where it belongs once the
+    /// branch is renamed to `to`.
+    fn branch_file_moves(&self, owner: &str, repo: &str, from: &str, to: &str) -> Vec<String> {
+        let address_pairs = [
+            (
+                GitHubResourceAddress::BranchProtection {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    branch: from.to_string(),
+                },
+                GitHubResourceAddress::BranchProtection {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    branch: to.to_string(),
+                },
+            ),
+            (
+                GitHubResourceAddress::Branch {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    branch: from.to_string(),
+                },
+                GitHubResourceAddress::Branch {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    branch: to.to_string(),
+                },
+            ),
+        ];
+
+        address_pairs
+            .into_iter()
+            .filter(|(from_addr, _)| self.prefix.join(from_addr.to_path_buf()).is_file())
+            .map(|(from_addr, to_addr)| {
+                format!(
+                    "  {} -> {}",
+                    from_addr.to_path_buf().display(),
+                    to_addr.to_path_buf().display()
+                )
+            })
+            .collect()
+    }
+
+    /// Plan the changes between two states of an existing repository.
+    /// Unarchiving comes before every other change, and archiving after all of them, since GitHub
+    /// rejects writes to an archived repository.
+    async fn plan_repository_update(
+        &self,
+        owner: &str,
+        repo: &str,
+        mut old_repo: resource::GitHubRepository,
+        mut new_repo: resource::GitHubRepository,
+    ) -> anyhow::Result<Vec<PlanResponseElement>> {
+        let mut res = Vec::new();
+
+        self.check_custom_properties(owner, repo, &new_repo)?;
+
+        // Like the creation-only options, this is cleared below since it can't be read back.
+        let default_branch_renamed_from = new_repo.default_branch_renamed_from.clone();
+
+        // Creation-only options can't be read back or changed on an existing repository.
+        let has_creation_options = new_repo.has_creation_options();
+        old_repo.clear_creation_options();
+        new_repo.clear_creation_options();
+
+        let fork_note = if old_repo.fork_of != new_repo.fork_of {
+            let note = format!(
+                "\nNote: fork_of can't be changed on an existing repository (currently {:?}); ignoring.",
+                old_repo.fork_of
+            );
+            new_repo.fork_of = old_repo.fork_of.clone();
+            note
+        } else {
+            String::new()
+        };
+
+        let unarchive = old_repo.archived && !new_repo.archived;
+        let archive = !old_repo.archived && new_repo.archived;
+        let stays_archived = old_repo.archived && new_repo.archived;
+        old_repo.archived = false;
+        new_repo.archived = false;
+
+        if stays_archived && old_repo != new_repo {
+            bail!(
+                "Repository {}/{} is archived, so GitHub will reject changes to it. Set archived: false to unarchive it first.",
+                owner,
+                repo
+            );
+        }
+
+        if unarchive {
+            res.push(connector_op!(
+                GitHubConnectorOp::UnarchiveRepository,
+                format!("Unarchive GitHub repository {}/{}", owner, repo)
+            ));
+        }
+
+        if old_repo.collaborators != new_repo.collaborators {
+            for (k, v) in &new_repo.collaborators {
+                if !old_repo.collaborators.contains_key(k) {
+                    res.push(connector_op!(
+                        GitHubConnectorOp::AddCollaborator(k.clone(), v.clone()),
+                        format!("Add Collaborator {:?} to repo {}/{} with role {:?}", k, owner, repo, v)
+                    ));
+                } else if old_repo.collaborators.get(k) != Some(v) {
+                    res.push(connector_op!(
+                        GitHubConnectorOp::UpdateCollaborator(k.clone(), v.clone()),
+                        format!("Update Collaborator {:?} on repo {}/{} to role {:?}", k, owner, repo, v)
+                    ));
+                }
+            }
+            for (k, _) in &old_repo.collaborators {
+                if !new_repo.collaborators.contains_key(k) {
+                    res.push(connector_op!(
+                        GitHubConnectorOp::RemoveCollaborator(k.clone()),
+                        format!("Remove Collaborator {:?} from repo {}/{}", k, owner, repo)
+                    ));
+                }
+            }
+        }
+
+        // Switching default_branch needs the new branch to exist already, so a rename is its own op.
+        if let Some(from) = default_branch_renamed_from
+            && old_repo.default_branch == from
+            && new_repo.default_branch != from
+        {
+            let to = new_repo.default_branch.clone();
+            let moves = self.branch_file_moves(owner, repo, &from, &to);
+            let moves_note = if moves.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "\nGitHub moves the branch's protection along with it. Move these files to match:\n{}",
+                    moves.join("\n")
+                )
+            };
+
+            res.push(connector_op!(
+                GitHubConnectorOp::RenameBranch(from.clone(), to.clone()),
+                format!(
+                    "Rename default branch of GitHub repository {}/{} from {} to {}{}",
+                    owner, repo, from, to, moves_note
+                )
+            ));
+
+            // The rename makes it the default branch, so it's left out of the update.
+            old_repo.default_branch = to;
+        }
+
+        if old_repo.custom_properties != new_repo.custom_properties {
+            let diff = diff_ron_values(&old_repo.custom_properties, &new_repo.custom_properties).unwrap_or_default();
+
+            // Properties that are no longer set are unset.
+            let mut values: BTreeMap<String, Option<resource::CustomPropertyValue>> = old_repo
+                .custom_properties
+                .keys()
+                .filter(|name| !new_repo.custom_properties.contains_key(*name))
+                .map(|name| (name.clone(), None))
+                .collect();
+            for (name, value) in &new_repo.custom_properties {
+                if old_repo.custom_properties.get(name) != Some(value) {
+                    values.insert(name.clone(), Some(value.clone()));
+                }
+            }
+
+            res.push(connector_op!(
+                GitHubConnectorOp::SetCustomPropertyValues(values),
+                format!("Set custom properties on GitHub repository {}/{}\n{}", owner, repo, diff)
+            ));
+        }
+
+        // Now that we've computed the collaborator and custom property updates manually, exclude them from the diff.
+        old_repo.collaborators = HashMap::new();
+        new_repo.collaborators = HashMap::new();
+        old_repo.custom_properties = BTreeMap::new();
+        new_repo.custom_properties = BTreeMap::new();
+
+        // Only update repository if other fields changed
+        if old_repo != new_repo {
+            let diff = diff_ron_values(&old_repo, &new_repo).unwrap_or_default();
+            let patch = repository_patch(&old_repo, &new_repo);
+
+            if patch != UpdateRepositoryRequest::default() {
+                res.push(connector_op!(
+                    GitHubConnectorOp::UpdateRepository(patch),
+                    format!("Update GitHub repository {}/{}\n{}{}", owner, repo, diff, fork_note)
+                ));
+            }
+
+            // Topics have their own endpoint.
+            if old_repo.topics != new_repo.topics {
+                res.push(connector_op!(
+                    GitHubConnectorOp::SetTopics(new_repo.topics.clone()),
+                    format!("Set topics on GitHub repository {}/{} to {:?}", owner, repo, new_repo.topics)
+                ));
+            }
+        }
+
+        if has_creation_options {
+            res.push(connector_op!(
+                GitHubConnectorOp::IgnoreCreationOptions,
+                format!(
+                    "Ignore the creation-only options of GitHub repository {}/{}, since it already exists. \
+                     template, auto_init, gitignore_template, license_template and fork_default_branch_only \
+                     only apply when creating a repository; remove them from repository.ron to drop this note.",
+                    owner, repo
+                )
+            ));
+        }
+
+        // Branch protection at sibling addresses defers to this plan while the archive state changes.
+        if archive || unarchive {
+            for (op, message) in self.plan_sibling_branch_protections(owner, repo).await? {
+                res.push(connector_op!(op, message));
+            }
+        }
+
+        if archive {
+            res.push(connector_op!(
+                GitHubConnectorOp::ArchiveRepository,
+                format!("Archive GitHub repository {}/{}", owner, repo)
+            ));
         }
 
         Ok(res)
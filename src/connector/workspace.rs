@@ -0,0 +1,116 @@
+use std::path::Path;
+
+use autoschematic_core::{connector::ResourceAddress, util::RON};
+use walkdir::WalkDir;
+
+use crate::{GitHubConnector, addr::GitHubResourceAddress, resource};
+
+impl GitHubConnector {
+    /// Read the desired state of a resource from the workspace, if its file exists.
+    pub fn read_workspace_file(&self, addr: &GitHubResourceAddress) -> anyhow::Result<Option<String>> {
+        let path = self.prefix.join(addr.to_path_buf());
+
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        Ok(Some(std::fs::read_to_string(path)?))
+    }
+
+    /// The branches of `owner/repo` that have a protection.ron in the workspace.
+    pub fn workspace_protected_branches(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>> {
+        let branches_dir = self.prefix.join("github").join(owner).join(repo).join("branches");
+        if !branches_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut branches = Vec::new();
+        for entry in WalkDir::new(&branches_dir).min_depth(2) {
+            let entry = entry?;
+            if entry.file_name() != "protection.ron" {
+                continue;
+            }
+
+            let Ok(relative) = entry.path().strip_prefix(&self.prefix) else {
+                continue;
+            };
+
+            if let Ok(GitHubResourceAddress::BranchProtection { branch, .. }) = GitHubResourceAddress::from_path(relative) {
+                branches.push(branch);
+            }
+        }
+
+        Ok(branches)
+    }
+
+    /// The branch rule patterns of `owner/repo` that have a file under branch_rules/ in the workspace.
+    pub fn workspace_branch_rules(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<String>> {
+        let rules_dir = self.prefix.join("github").join(owner).join(repo).join("branch_rules");
+        if !rules_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut patterns = Vec::new();
+        for entry in WalkDir::new(&rules_dir).min_depth(1) {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let Ok(relative) = entry.path().strip_prefix(&self.prefix) else {
+                continue;
+            };
+
+            if let Ok(GitHubResourceAddress::BranchRule { pattern, .. }) = GitHubResourceAddress::from_path(relative) {
+                patterns.push(pattern);
+            }
+        }
+
+        Ok(patterns)
+    }
+
+    /// Find a repository.ron in the workspace that declares `moved_from: Some("{owner}/{repo}")`,
+    /// returning the owner and name it's moving to.
+    pub fn find_moved_repository(&self, owner: &str, repo: &str) -> anyhow::Result<Option<(String, String)>> {
+        let github_dir = self.prefix.join("github");
+        if !github_dir.is_dir() {
+            return Ok(None);
+        }
+
+        let previous = format!("{}/{}", owner, repo);
+
+        for entry in WalkDir::new(&github_dir).min_depth(3).max_depth(3) {
+            let entry = entry?;
+            if entry.file_name() != "repository.ron" {
+                continue;
+            }
+
+            let Ok(relative) = entry.path().strip_prefix(&self.prefix) else {
+                continue;
+            };
+
+            let Ok(GitHubResourceAddress::Repository {
+                owner: new_owner,
+                repo: new_repo,
+            }) = GitHubResourceAddress::from_path(Path::new(relative))
+            else {
+                continue;
+            };
+
+            let repo_str = std::fs::read_to_string(entry.path())?;
+            let Ok(repo_config) = RON.from_str::<resource::GitHubRepository>(&repo_str) else {
+                tracing::warn!("Failed to parse {}", entry.path().display());
+                continue;
+            };
+
+            if repo_config
+                .moved_from
+                .is_some_and(|moved_from| moved_from.eq_ignore_ascii_case(&previous))
+            {
+                return Ok(Some((new_owner, new_repo)));
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -4,7 +4,33 @@ use async_trait::async_trait;
 use octocrab::{Octocrab, Page, Result};
 use serde::{Deserialize, Serialize};
 
-use crate::resource::{CollaboratorPrincipal, Role};
+use crate::resource::{CollaboratorPrincipal, CustomPropertyValue, Role};
+
+/// Whether a request failed because the resource doesn't exist.
+pub fn is_not_found(e: &octocrab::Error) -> bool {
+    matches!(e, octocrab::Error::GitHub { source, .. } if source.status_code.as_u16() == 404)
+}
+
+// The messages GitHub gives while a Pages custom domain's DNS check or certificate is still pending.
+// Anything else, like an invalid domain or one that's already taken, is a real failure.
+const PENDING_DOMAIN_MESSAGES: &[&str] = &[
+    "the certificate does not exist yet",
+    "the certificate is being provisioned",
+    "dns check in progress",
+    "domain is not yet verified",
+];
+
+/// Whether GitHub rejected a Pages custom domain or HTTPS enforcement because the domain's DNS isn't
+/// verified, or its certificate isn't issued, yet. These clear up on their own once DNS propagates.
+pub fn is_pending_domain_error(e: &octocrab::Error) -> bool {
+    match e {
+        octocrab::Error::GitHub { source, .. } if matches!(source.status_code.as_u16(), 400 | 404 | 422) => {
+            let message = source.message.to_lowercase();
+            PENDING_DOMAIN_MESSAGES.iter().any(|pending| message.contains(pending))
+        }
+        _ => false,
+    }
+}
 
 // GitHub API response structures for branch protection
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,12 +46,28 @@ pub struct GitHubBranchProtection {
     pub required_conversation_resolution: Option<GitHubBooleanSetting>,
     pub lock_branch: Option<GitHubBooleanSetting>,
     pub allow_fork_syncing: Option<GitHubBooleanSetting>,
+    pub required_signatures: Option<GitHubBooleanSetting>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GitHubRequiredStatusChecks {
     pub strict: bool,
     pub contexts: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<GitHubStatusCheck>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubStatusCheck {
+    pub context: String,
+    // -1 allows any app to report the check; null or omitted picks whichever app reported it last.
+    pub app_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubAppDetails {
+    pub id: i64,
+    pub slug: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,15 +81,56 @@ pub struct GitHubPullRequestReviewEnforcement {
     pub dismiss_stale_reviews: Option<bool>,
     pub require_code_owner_reviews: Option<bool>,
     pub require_last_push_approval: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_review_allowances")]
+    pub dismissal_restrictions: Option<GitHubActorAllowances>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_review_allowances")]
+    pub bypass_pull_request_allowances: Option<GitHubActorAllowances>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct GitHubBranchRestrictions {
+// The API lifts a review restriction given an empty object, rather than one with empty lists.
+fn serialize_review_allowances<S: serde::Serializer>(
+    allowances: &Option<GitHubActorAllowances>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+
+    match allowances {
+        Some(allowances) if allowances.users.is_empty() && allowances.teams.is_empty() && allowances.apps.is_empty() => {
+            serializer.serialize_map(Some(0))?.end()
+        }
+        allowances => allowances.serialize(serializer),
+    }
+}
+
+// Responses list full user, team and app objects, but requests take logins and slugs, so this
+// serializes to plain strings.
+#[derive(Debug, Default, Deserialize)]
+pub struct GitHubActorAllowances {
+    #[serde(default)]
     pub users: Vec<GitHubUser>,
+    #[serde(default)]
     pub teams: Vec<GitHubTeam>,
+    #[serde(default)]
     pub apps: Vec<GitHubApp>,
 }
 
+impl Serialize for GitHubActorAllowances {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("GitHubActorAllowances", 3)?;
+        state.serialize_field("users", &self.users.iter().map(|u| &u.login).collect::<Vec<_>>())?;
+        state.serialize_field("teams", &self.teams.iter().map(|t| &t.slug).collect::<Vec<_>>())?;
+        state.serialize_field("apps", &self.apps.iter().map(|a| &a.slug).collect::<Vec<_>>())?;
+        state.end()
+    }
+}
+
+// Push restrictions take the same users, teams and apps as review allowances, and are likewise written as logins and slugs.
+pub type GitHubBranchRestrictions = GitHubActorAllowances;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GitHubUser {
     pub login: String,
@@ -56,11 +139,13 @@ pub struct GitHubUser {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GitHubTeam {
     pub name: String,
+    pub slug: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GitHubApp {
     pub name: String,
+    pub slug: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -92,7 +177,25 @@ pub trait BranchProtectionExt {
 #[async_trait]
 impl BranchProtectionExt for Octocrab {
     async fn get_branch_protection(&self, owner: &str, repo: &str, branch: &str) -> Result<GitHubBranchProtection> {
-        let route = format!("/repos/{}/{}/branches/{}/protection", owner, repo, branch);
+        let route = format!(
+            "/repos/{}/{}/branches/{}/protection",
+            owner,
+            repo,
+            urlencoding::encode(branch)
+        );
+        self.get(route, None::<&()>).await
+    }
+}
+
+#[async_trait]
+pub trait AppExt {
+    async fn get_app(&self, app_slug: &str) -> Result<GitHubAppDetails>;
+}
+
+#[async_trait]
+impl AppExt for Octocrab {
+    async fn get_app(&self, app_slug: &str) -> Result<GitHubAppDetails> {
+        let route = format!("/apps/{}", app_slug);
         self.get(route, None::<&()>).await
     }
 }
@@ -115,6 +218,12 @@ impl CollaboratorExt for Octocrab {
 pub struct GitHubBranch {
     pub name: String,
     pub protected: bool,
+    pub commit: GitHubCommitSha,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubCommitSha {
+    pub sha: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -229,24 +338,82 @@ pub struct CreateRepositoryRequest {
     pub allow_auto_merge: bool,
     pub delete_branch_on_merge: bool,
     pub default_branch: Option<String>,
+    pub is_template: bool,
+    pub auto_init: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gitignore_template: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_template: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GenerateRepositoryRequest {
+    pub owner: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub include_all_branches: bool,
+    pub private: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransferRepositoryRequest {
+    pub new_owner: String,
+    pub new_name: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+pub struct ForkRepositoryRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    pub name: String,
+    pub default_branch_only: bool,
+}
+
+// Fields left as None are omitted from the PATCH body and keep their current value.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct UpdateRepositoryRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub homepage: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub private: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub has_issues: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub has_projects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub has_wiki: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_squash_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_merge_commit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_rebase_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_auto_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub delete_branch_on_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub default_branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub archived: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_template: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplaceTopicsRequest {
+    pub names: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct RepositoryOpenCounts {
+    pub pull_requests: u64,
+    pub issues: u64,
 }
 
 // Structures for branch protection operations
@@ -265,6 +432,19 @@ pub struct CreateBranchProtectionRequest {
     pub allow_fork_syncing: Option<bool>,
 }
 
+// Structures for branch operations
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateRefRequest {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub sha: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RenameBranchRequest {
+    pub new_name: String,
+}
+
 // Structures for collaborator operations
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AddCollaboratorRequest {
@@ -280,6 +460,18 @@ pub struct AddTeamCollaboratorRequest {
 pub trait RepositoryOpsExt {
     async fn create_repository(&self, owner: &str, repo_data: &CreateRepositoryRequest)
     -> Result<octocrab::models::Repository>;
+    async fn generate_repository(
+        &self,
+        template_owner: &str,
+        template_repo: &str,
+        repo_data: &GenerateRepositoryRequest,
+    ) -> Result<octocrab::models::Repository>;
+    async fn fork_repository(
+        &self,
+        upstream_owner: &str,
+        upstream_repo: &str,
+        fork_data: &ForkRepositoryRequest,
+    ) -> Result<octocrab::models::Repository>;
     async fn update_repository(
         &self,
         owner: &str,
@@ -287,6 +479,14 @@ pub trait RepositoryOpsExt {
         repo_data: &UpdateRepositoryRequest,
     ) -> Result<octocrab::models::Repository>;
     async fn delete_repository(&self, owner: &str, repo: &str) -> Result<()>;
+    async fn transfer_repository(
+        &self,
+        owner: &str,
+        repo: &str,
+        transfer_data: &TransferRepositoryRequest,
+    ) -> Result<octocrab::models::Repository>;
+    async fn replace_topics(&self, owner: &str, repo: &str, topics_data: &ReplaceTopicsRequest) -> Result<serde_json::Value>;
+    async fn get_open_counts(&self, owner: &str, repo: &str) -> anyhow::Result<RepositoryOpenCounts>;
 }
 
 #[async_trait]
@@ -300,6 +500,26 @@ impl RepositoryOpsExt for Octocrab {
         self.post(route, Some(repo_data)).await
     }
 
+    async fn generate_repository(
+        &self,
+        template_owner: &str,
+        template_repo: &str,
+        repo_data: &GenerateRepositoryRequest,
+    ) -> Result<octocrab::models::Repository> {
+        let route = format!("/repos/{}/{}/generate", template_owner, template_repo);
+        self.post(route, Some(repo_data)).await
+    }
+
+    async fn fork_repository(
+        &self,
+        upstream_owner: &str,
+        upstream_repo: &str,
+        fork_data: &ForkRepositoryRequest,
+    ) -> Result<octocrab::models::Repository> {
+        let route = format!("/repos/{}/{}/forks", upstream_owner, upstream_repo);
+        self.post(route, Some(fork_data)).await
+    }
+
     async fn update_repository(
         &self,
         owner: &str,
@@ -314,6 +534,39 @@ impl RepositoryOpsExt for Octocrab {
         let route = format!("/repos/{}/{}", owner, repo);
         self.delete(route, None::<&()>).await
     }
+
+    async fn transfer_repository(
+        &self,
+        owner: &str,
+        repo: &str,
+        transfer_data: &TransferRepositoryRequest,
+    ) -> Result<octocrab::models::Repository> {
+        let route = format!("/repos/{}/{}/transfer", owner, repo);
+        self.post(route, Some(transfer_data)).await
+    }
+
+    async fn replace_topics(&self, owner: &str, repo: &str, topics_data: &ReplaceTopicsRequest) -> Result<serde_json::Value> {
+        let route = format!("/repos/{}/{}/topics", owner, repo);
+        self.put(route, Some(topics_data)).await
+    }
+
+    async fn get_open_counts(&self, owner: &str, repo: &str) -> anyhow::Result<RepositoryOpenCounts> {
+        let query = serde_json::json!({
+            "query": "query($owner: String!, $name: String!) { repository(owner: $owner, name: $name) { pullRequests(states: OPEN) { totalCount } issues(states: OPEN) { totalCount } } }",
+            "variables": { "owner": owner, "name": repo },
+        });
+
+        let response: serde_json::Value = self.graphql(&query).await?;
+        let repository = &response["data"]["repository"];
+        if repository.is_null() {
+            anyhow::bail!("GraphQL query for {}/{} returned no repository: {}", owner, repo, response["errors"]);
+        }
+
+        Ok(RepositoryOpenCounts {
+            pull_requests: repository["pullRequests"]["totalCount"].as_u64().unwrap_or_default(),
+            issues: repository["issues"]["totalCount"].as_u64().unwrap_or_default(),
+        })
+    }
 }
 
 #[async_trait]
@@ -333,6 +586,8 @@ pub trait BranchProtectionOpsExt {
         protection_data: &CreateBranchProtectionRequest,
     ) -> Result<GitHubBranchProtection>;
     async fn delete_branch_protection(&self, owner: &str, repo: &str, branch: &str) -> Result<()>;
+    async fn enable_required_signatures(&self, owner: &str, repo: &str, branch: &str) -> Result<GitHubBooleanSetting>;
+    async fn disable_required_signatures(&self, owner: &str, repo: &str, branch: &str) -> Result<()>;
 }
 
 #[async_trait]
@@ -344,7 +599,12 @@ impl BranchProtectionOpsExt for Octocrab {
         branch: &str,
         protection_data: &CreateBranchProtectionRequest,
     ) -> Result<GitHubBranchProtection> {
-        let route = format!("/repos/{}/{}/branches/{}/protection", owner, repo, branch);
+        let route = format!(
+            "/repos/{}/{}/branches/{}/protection",
+            owner,
+            repo,
+            urlencoding::encode(branch)
+        );
         self.put(route, Some(protection_data)).await
     }
 
@@ -355,16 +615,121 @@ impl BranchProtectionOpsExt for Octocrab {
         branch: &str,
         protection_data: &CreateBranchProtectionRequest,
     ) -> Result<GitHubBranchProtection> {
-        let route = format!("/repos/{}/{}/branches/{}/protection", owner, repo, branch);
+        let route = format!(
+            "/repos/{}/{}/branches/{}/protection",
+            owner,
+            repo,
+            urlencoding::encode(branch)
+        );
         self.put(route, Some(protection_data)).await
     }
 
     async fn delete_branch_protection(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
-        let route = format!("/repos/{}/{}/branches/{}/protection", owner, repo, branch);
+        let route = format!(
+            "/repos/{}/{}/branches/{}/protection",
+            owner,
+            repo,
+            urlencoding::encode(branch)
+        );
+        self.delete(route, None::<&()>).await
+    }
+
+    async fn enable_required_signatures(&self, owner: &str, repo: &str, branch: &str) -> Result<GitHubBooleanSetting> {
+        let route = format!(
+            "/repos/{}/{}/branches/{}/protection/required_signatures",
+            owner,
+            repo,
+            urlencoding::encode(branch)
+        );
+        self.post(route, None::<&()>).await
+    }
+
+    async fn disable_required_signatures(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        let route = format!(
+            "/repos/{}/{}/branches/{}/protection/required_signatures",
+            owner,
+            repo,
+            urlencoding::encode(branch)
+        );
         self.delete(route, None::<&()>).await
     }
 }
 
+#[async_trait]
+pub trait BranchOpsExt {
+    async fn get_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<GitHubBranch>;
+    async fn get_commit_sha(&self, owner: &str, repo: &str, reference: &str) -> Result<GitHubCommitSha>;
+    async fn create_branch(&self, owner: &str, repo: &str, branch: &str, sha: &str) -> Result<serde_json::Value>;
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()>;
+    async fn rename_branch(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        rename_data: &RenameBranchRequest,
+    ) -> Result<GitHubBranch>;
+    async fn count_open_pulls_into(&self, owner: &str, repo: &str, branch: &str) -> Result<usize>;
+}
+
+/// Percent-encode each segment of a branch name, keeping the slashes that git ref routes expect.
+fn encode_ref_path(branch: &str) -> String {
+    branch
+        .split('/')
+        .map(|segment| urlencoding::encode(segment))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[async_trait]
+impl BranchOpsExt for Octocrab {
+    async fn get_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<GitHubBranch> {
+        let route = format!("/repos/{}/{}/branches/{}", owner, repo, urlencoding::encode(branch));
+        self.get(route, None::<&()>).await
+    }
+
+    async fn get_commit_sha(&self, owner: &str, repo: &str, reference: &str) -> Result<GitHubCommitSha> {
+        let route = format!("/repos/{}/{}/commits/{}", owner, repo, urlencoding::encode(reference));
+        self.get(route, None::<&()>).await
+    }
+
+    async fn create_branch(&self, owner: &str, repo: &str, branch: &str, sha: &str) -> Result<serde_json::Value> {
+        let route = format!("/repos/{}/{}/git/refs", owner, repo);
+        let request = CreateRefRequest {
+            git_ref: format!("refs/heads/{}", branch),
+            sha: sha.to_string(),
+        };
+        self.post(route, Some(&request)).await
+    }
+
+    async fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<()> {
+        let route = format!("/repos/{}/{}/git/refs/heads/{}", owner, repo, encode_ref_path(branch));
+        self.delete(route, None::<&()>).await
+    }
+
+    async fn rename_branch(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        rename_data: &RenameBranchRequest,
+    ) -> Result<GitHubBranch> {
+        let route = format!("/repos/{}/{}/branches/{}/rename", owner, repo, urlencoding::encode(branch));
+        self.post(route, Some(rename_data)).await
+    }
+
+    async fn count_open_pulls_into(&self, owner: &str, repo: &str, branch: &str) -> Result<usize> {
+        let page = self
+            .pulls(owner, repo)
+            .list()
+            .state(octocrab::params::State::Open)
+            .base(branch)
+            .per_page(100)
+            .send()
+            .await?;
+        Ok(self.all_pages(page).await?.len())
+    }
+}
+
 #[async_trait]
 pub trait CollaboratorOpsExt {
     async fn add_collaborator(
@@ -455,3 +820,871 @@ impl CollaboratorOpsExt for Octocrab {
         self.delete(route, None::<&()>).await
     }
 }
+
+// Structures for custom repository properties
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubCustomProperty {
+    pub property_name: String,
+    pub value_type: String,
+    #[serde(default)]
+    pub required: bool,
+    // Sent as null to clear them, so these aren't skipped when unset.
+    #[serde(default)]
+    pub default_value: Option<CustomPropertyValue>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub allowed_values: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpsertCustomPropertiesRequest {
+    pub properties: Vec<GitHubCustomProperty>,
+}
+
+// A null value unsets the property on the repository.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubCustomPropertyValue {
+    pub property_name: String,
+    pub value: Option<CustomPropertyValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetCustomPropertyValuesRequest {
+    pub properties: Vec<GitHubCustomPropertyValue>,
+}
+
+#[async_trait]
+pub trait CustomPropertyExt {
+    async fn get_custom_properties(&self, org: &str) -> Result<Vec<GitHubCustomProperty>>;
+    async fn upsert_custom_properties(
+        &self,
+        org: &str,
+        properties_data: &UpsertCustomPropertiesRequest,
+    ) -> Result<Vec<GitHubCustomProperty>>;
+    async fn delete_custom_property(&self, org: &str, property_name: &str) -> Result<()>;
+    async fn get_custom_property_values(&self, owner: &str, repo: &str) -> Result<Vec<GitHubCustomPropertyValue>>;
+    async fn set_custom_property_values(
+        &self,
+        owner: &str,
+        repo: &str,
+        values_data: &SetCustomPropertyValuesRequest,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+impl CustomPropertyExt for Octocrab {
+    async fn get_custom_properties(&self, org: &str) -> Result<Vec<GitHubCustomProperty>> {
+        let route = format!("/orgs/{}/properties/schema", org);
+        self.get(route, None::<&()>).await
+    }
+
+    async fn upsert_custom_properties(
+        &self,
+        org: &str,
+        properties_data: &UpsertCustomPropertiesRequest,
+    ) -> Result<Vec<GitHubCustomProperty>> {
+        let route = format!("/orgs/{}/properties/schema", org);
+        self.patch(route, Some(properties_data)).await
+    }
+
+    async fn delete_custom_property(&self, org: &str, property_name: &str) -> Result<()> {
+        let route = format!("/orgs/{}/properties/schema/{}", org, urlencoding::encode(property_name));
+        self.delete(route, None::<&()>).await
+    }
+
+    async fn get_custom_property_values(&self, owner: &str, repo: &str) -> Result<Vec<GitHubCustomPropertyValue>> {
+        let route = format!("/repos/{}/{}/properties/values", owner, repo);
+        self.get(route, None::<&()>).await
+    }
+
+    async fn set_custom_property_values(
+        &self,
+        owner: &str,
+        repo: &str,
+        values_data: &SetCustomPropertyValuesRequest,
+    ) -> Result<()> {
+        let route = format!("/repos/{}/{}/properties/values", owner, repo);
+        self.patch(route, Some(values_data)).await
+    }
+}
+
+// Structures for GitHub Pages
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubPages {
+    pub status: Option<String>,
+    pub html_url: Option<String>,
+    pub cname: Option<String>,
+    pub build_type: Option<String>,
+    pub source: Option<GitHubPagesSource>,
+    pub public: Option<bool>,
+    pub https_enforced: Option<bool>,
+    pub https_certificate: Option<GitHubPagesCertificate>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubPagesSource {
+    pub branch: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubPagesCertificate {
+    pub state: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreatePagesRequest {
+    pub build_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<GitHubPagesSource>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdatePagesRequest {
+    /// Sent as null to remove the custom domain, and left out to keep it as it is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cname: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub https_enforced: Option<bool>,
+    pub build_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<GitHubPagesSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public: Option<bool>,
+}
+
+// Structures for code scanning default setup
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubCodeScanningDefaultSetup {
+    pub state: String,
+    #[serde(default)]
+    pub languages: Vec<String>,
+    pub query_suite: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateCodeScanningDefaultSetupRequest {
+    pub state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_suite: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub languages: Option<Vec<String>>,
+}
+
+/// The response to a default setup update. The run is only started when something changed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubDefaultSetupRun {
+    pub run_id: Option<u64>,
+    pub run_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubWorkflowRun {
+    pub status: Option<String>,
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>,
+}
+
+// Structures for interaction limits
+/// A repository or organization's interaction limit. Every field is missing when no limit is in place,
+/// which includes once a limit has expired.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubInteractionLimits {
+    pub limit: Option<String>,
+    pub origin: Option<String>,
+    pub expires_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetInteractionLimitsRequest {
+    pub limit: String,
+    pub expiry: String,
+}
+
+/// Where an interaction limit lives.
+pub enum InteractionLimitsScope<'a> {
+    Repository { owner: &'a str, repo: &'a str },
+    Organization { org: &'a str },
+}
+
+impl InteractionLimitsScope<'_> {
+    pub fn route(&self) -> String {
+        match self {
+            InteractionLimitsScope::Repository { owner, repo } => format!("/repos/{}/{}/interaction-limits", owner, repo),
+            InteractionLimitsScope::Organization { org } => format!("/orgs/{}/interaction-limits", org),
+        }
+    }
+
+    /// Whether `limits` were set at this scope. A repository also reports the limit of its organization
+    /// or owning user, which can't be changed or removed through the repository.
+    pub fn owns(&self, limits: &GitHubInteractionLimits) -> bool {
+        match self {
+            InteractionLimitsScope::Repository { .. } => limits.origin.as_deref() == Some("repository"),
+            InteractionLimitsScope::Organization { .. } => true,
+        }
+    }
+}
+
+// Structures for GitHub Actions permissions
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GitHubActionsPermissions {
+    // Repositories report `enabled`, organizations `enabled_repositories`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled_repositories: Option<String>,
+    // Omitted while Actions is disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_actions: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubSelectedActions {
+    pub github_owned_allowed: bool,
+    pub verified_allowed: bool,
+    pub patterns_allowed: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubWorkflowPermissions {
+    pub default_workflow_permissions: String,
+    pub can_approve_pull_request_reviews: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubRepositoryName {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubSelectedRepositories {
+    pub total_count: u64,
+    pub repositories: Vec<GitHubRepositoryName>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetSelectedRepositoriesRequest {
+    pub selected_repository_ids: Vec<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubArtifactRetention {
+    pub days: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubForkPrApproval {
+    pub approval_policy: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubActionsAccess {
+    pub access_level: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubOidcSubjectClaim {
+    pub use_default: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_claim_keys: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubRunnerGroup {
+    pub id: u64,
+    pub name: String,
+    pub visibility: String,
+    #[serde(default)]
+    pub default: bool,
+    #[serde(default)]
+    pub inherited: bool,
+    pub allows_public_repositories: bool,
+    #[serde(default)]
+    pub restricted_to_workflows: bool,
+    #[serde(default)]
+    pub selected_workflows: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GitHubRunnerGroups {
+    pub total_count: u64,
+    pub runner_groups: Vec<GitHubRunnerGroup>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RunnerGroupRequest {
+    pub name: String,
+    pub visibility: String,
+    /// Only accepted on create; the repositories of an existing group are set separately.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected_repository_ids: Option<Vec<u64>>,
+    pub allows_public_repositories: bool,
+    pub restricted_to_workflows: bool,
+    pub selected_workflows: Vec<String>,
+}
+
+/// Where a set of Actions settings lives. Repositories and organizations share the same routes
+/// under different prefixes.
+pub enum ActionsScope<'a> {
+    Repository { owner: &'a str, repo: &'a str },
+    Organization { org: &'a str },
+}
+
+impl ActionsScope<'_> {
+    pub fn route(&self, path: &str) -> String {
+        match self {
+            ActionsScope::Repository { owner, repo } => format!("/repos/{}/{}/actions/{}", owner, repo, path),
+            ActionsScope::Organization { org } => format!("/orgs/{}/actions/{}", org, path),
+        }
+    }
+}
+
+#[async_trait]
+pub trait ActionsPermissionsExt {
+    async fn get_actions_permissions(&self, scope: &ActionsScope<'_>) -> Result<GitHubActionsPermissions>;
+    async fn set_actions_permissions(&self, scope: &ActionsScope<'_>, permissions_data: &GitHubActionsPermissions) -> Result<()>;
+    async fn get_selected_actions(&self, scope: &ActionsScope<'_>) -> Result<GitHubSelectedActions>;
+    async fn set_selected_actions(&self, scope: &ActionsScope<'_>, selected_data: &GitHubSelectedActions) -> Result<()>;
+    async fn get_workflow_permissions(&self, scope: &ActionsScope<'_>) -> Result<GitHubWorkflowPermissions>;
+    async fn set_workflow_permissions(&self, scope: &ActionsScope<'_>, workflow_data: &GitHubWorkflowPermissions) -> Result<()>;
+    async fn list_actions_enabled_repositories(&self, org: &str) -> Result<Vec<String>>;
+    async fn set_actions_enabled_repositories(&self, org: &str, repos_data: &SetSelectedRepositoriesRequest) -> Result<()>;
+}
+
+#[async_trait]
+impl ActionsPermissionsExt for Octocrab {
+    async fn get_actions_permissions(&self, scope: &ActionsScope<'_>) -> Result<GitHubActionsPermissions> {
+        self.get(scope.route("permissions"), None::<&()>).await
+    }
+
+    async fn set_actions_permissions(&self, scope: &ActionsScope<'_>, permissions_data: &GitHubActionsPermissions) -> Result<()> {
+        self.put(scope.route("permissions"), Some(permissions_data)).await
+    }
+
+    async fn get_selected_actions(&self, scope: &ActionsScope<'_>) -> Result<GitHubSelectedActions> {
+        self.get(scope.route("permissions/selected-actions"), None::<&()>).await
+    }
+
+    async fn set_selected_actions(&self, scope: &ActionsScope<'_>, selected_data: &GitHubSelectedActions) -> Result<()> {
+        let route = scope.route("permissions/selected-actions");
+        self.put(route, Some(selected_data)).await
+    }
+
+    async fn get_workflow_permissions(&self, scope: &ActionsScope<'_>) -> Result<GitHubWorkflowPermissions> {
+        self.get(scope.route("permissions/workflow"), None::<&()>).await
+    }
+
+    async fn set_workflow_permissions(&self, scope: &ActionsScope<'_>, workflow_data: &GitHubWorkflowPermissions) -> Result<()> {
+        self.put(scope.route("permissions/workflow"), Some(workflow_data)).await
+    }
+
+    async fn list_actions_enabled_repositories(&self, org: &str) -> Result<Vec<String>> {
+        let route = ActionsScope::Organization { org }.route("permissions/repositories");
+        list_selected_repositories(self, &route).await
+    }
+
+    async fn set_actions_enabled_repositories(&self, org: &str, repos_data: &SetSelectedRepositoriesRequest) -> Result<()> {
+        let route = ActionsScope::Organization { org }.route("permissions/repositories");
+        self.put(route, Some(repos_data)).await
+    }
+}
+
+#[async_trait]
+pub trait ActionsSettingsExt {
+    async fn get_artifact_retention(&self, scope: &ActionsScope<'_>) -> Result<GitHubArtifactRetention>;
+    async fn set_artifact_retention(&self, scope: &ActionsScope<'_>, retention_data: &GitHubArtifactRetention) -> Result<()>;
+    async fn get_fork_pr_approval(&self, scope: &ActionsScope<'_>) -> Result<GitHubForkPrApproval>;
+    async fn set_fork_pr_approval(&self, scope: &ActionsScope<'_>, approval_data: &GitHubForkPrApproval) -> Result<()>;
+    async fn get_actions_access(&self, scope: &ActionsScope<'_>) -> Result<GitHubActionsAccess>;
+    async fn set_actions_access(&self, scope: &ActionsScope<'_>, access_data: &GitHubActionsAccess) -> Result<()>;
+    async fn get_oidc_subject_claim(&self, scope: &ActionsScope<'_>) -> Result<GitHubOidcSubjectClaim>;
+    async fn set_oidc_subject_claim(&self, scope: &ActionsScope<'_>, claim_data: &GitHubOidcSubjectClaim) -> Result<()>;
+}
+
+#[async_trait]
+impl ActionsSettingsExt for Octocrab {
+    async fn get_artifact_retention(&self, scope: &ActionsScope<'_>) -> Result<GitHubArtifactRetention> {
+        let route = scope.route("permissions/artifact-and-log-retention");
+        self.get(route, None::<&()>).await
+    }
+
+    async fn set_artifact_retention(&self, scope: &ActionsScope<'_>, retention_data: &GitHubArtifactRetention) -> Result<()> {
+        let route = scope.route("permissions/artifact-and-log-retention");
+        self.put(route, Some(retention_data)).await
+    }
+
+    async fn get_fork_pr_approval(&self, scope: &ActionsScope<'_>) -> Result<GitHubForkPrApproval> {
+        let route = scope.route("permissions/fork-pr-contributor-approval");
+        self.get(route, None::<&()>).await
+    }
+
+    async fn set_fork_pr_approval(&self, scope: &ActionsScope<'_>, approval_data: &GitHubForkPrApproval) -> Result<()> {
+        let route = scope.route("permissions/fork-pr-contributor-approval");
+        self.put(route, Some(approval_data)).await
+    }
+
+    async fn get_actions_access(&self, scope: &ActionsScope<'_>) -> Result<GitHubActionsAccess> {
+        self.get(scope.route("permissions/access"), None::<&()>).await
+    }
+
+    async fn set_actions_access(&self, scope: &ActionsScope<'_>, access_data: &GitHubActionsAccess) -> Result<()> {
+        self.put(scope.route("permissions/access"), Some(access_data)).await
+    }
+
+    async fn get_oidc_subject_claim(&self, scope: &ActionsScope<'_>) -> Result<GitHubOidcSubjectClaim> {
+        self.get(scope.route("oidc/customization/sub"), None::<&()>).await
+    }
+
+    async fn set_oidc_subject_claim(&self, scope: &ActionsScope<'_>, claim_data: &GitHubOidcSubjectClaim) -> Result<()> {
+        self.put(scope.route("oidc/customization/sub"), Some(claim_data)).await
+    }
+}
+
+#[async_trait]
+pub trait PagesExt {
+    async fn get_pages(&self, owner: &str, repo: &str) -> Result<GitHubPages>;
+    async fn create_pages(&self, owner: &str, repo: &str, pages_data: &CreatePagesRequest) -> Result<GitHubPages>;
+    async fn update_pages(&self, owner: &str, repo: &str, pages_data: &UpdatePagesRequest) -> Result<()>;
+    async fn delete_pages(&self, owner: &str, repo: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl PagesExt for Octocrab {
+    async fn get_pages(&self, owner: &str, repo: &str) -> Result<GitHubPages> {
+        let route = format!("/repos/{}/{}/pages", owner, repo);
+        self.get(route, None::<&()>).await
+    }
+
+    async fn create_pages(&self, owner: &str, repo: &str, pages_data: &CreatePagesRequest) -> Result<GitHubPages> {
+        let route = format!("/repos/{}/{}/pages", owner, repo);
+        self.post(route, Some(pages_data)).await
+    }
+
+    async fn update_pages(&self, owner: &str, repo: &str, pages_data: &UpdatePagesRequest) -> Result<()> {
+        let route = format!("/repos/{}/{}/pages", owner, repo);
+        self.put(route, Some(pages_data)).await
+    }
+
+    async fn delete_pages(&self, owner: &str, repo: &str) -> Result<()> {
+        let route = format!("/repos/{}/{}/pages", owner, repo);
+        self.delete(route, None::<&()>).await
+    }
+}
+
+#[async_trait]
+pub trait CodeScanningExt {
+    async fn get_code_scanning_default_setup(&self, owner: &str, repo: &str) -> Result<GitHubCodeScanningDefaultSetup>;
+    async fn update_code_scanning_default_setup(
+        &self,
+        owner: &str,
+        repo: &str,
+        setup_data: &UpdateCodeScanningDefaultSetupRequest,
+    ) -> Result<GitHubDefaultSetupRun>;
+    async fn get_workflow_run(&self, owner: &str, repo: &str, run_id: u64) -> Result<GitHubWorkflowRun>;
+}
+
+#[async_trait]
+impl CodeScanningExt for Octocrab {
+    async fn get_code_scanning_default_setup(&self, owner: &str, repo: &str) -> Result<GitHubCodeScanningDefaultSetup> {
+        let route = format!("/repos/{}/{}/code-scanning/default-setup", owner, repo);
+        self.get(route, None::<&()>).await
+    }
+
+    async fn update_code_scanning_default_setup(
+        &self,
+        owner: &str,
+        repo: &str,
+        setup_data: &UpdateCodeScanningDefaultSetupRequest,
+    ) -> Result<GitHubDefaultSetupRun> {
+        let route = format!("/repos/{}/{}/code-scanning/default-setup", owner, repo);
+        self.patch(route, Some(setup_data)).await
+    }
+
+    async fn get_workflow_run(&self, owner: &str, repo: &str, run_id: u64) -> Result<GitHubWorkflowRun> {
+        let route = format!("/repos/{}/{}/actions/runs/{}", owner, repo, run_id);
+        self.get(route, None::<&()>).await
+    }
+}
+
+#[async_trait]
+pub trait InteractionLimitsExt {
+    async fn get_interaction_limits(&self, scope: &InteractionLimitsScope<'_>) -> Result<GitHubInteractionLimits>;
+    async fn set_interaction_limits(
+        &self,
+        scope: &InteractionLimitsScope<'_>,
+        limits_data: &SetInteractionLimitsRequest,
+    ) -> Result<GitHubInteractionLimits>;
+    async fn remove_interaction_limits(&self, scope: &InteractionLimitsScope<'_>) -> Result<()>;
+}
+
+#[async_trait]
+impl InteractionLimitsExt for Octocrab {
+    async fn get_interaction_limits(&self, scope: &InteractionLimitsScope<'_>) -> Result<GitHubInteractionLimits> {
+        self.get(scope.route(), None::<&()>).await
+    }
+
+    async fn set_interaction_limits(
+        &self,
+        scope: &InteractionLimitsScope<'_>,
+        limits_data: &SetInteractionLimitsRequest,
+    ) -> Result<GitHubInteractionLimits> {
+        self.put(scope.route(), Some(limits_data)).await
+    }
+
+    async fn remove_interaction_limits(&self, scope: &InteractionLimitsScope<'_>) -> Result<()> {
+        self.delete(scope.route(), None::<&()>).await
+    }
+}
+
+/// List the names in a paged set of selected repositories. These endpoints return a count and a list
+/// rather than a plain array, so they aren't a Page.
+async fn list_selected_repositories(client: &Octocrab, route: &str) -> Result<Vec<String>> {
+    #[derive(serde::Serialize)]
+    struct PageQuery {
+        per_page: u8,
+        page: u32,
+    }
+
+    let mut names = Vec::new();
+    for page in 1.. {
+        let response: GitHubSelectedRepositories = client.get(route, Some(&PageQuery { per_page: 100, page })).await?;
+        let done = response.repositories.len() < 100;
+        names.extend(response.repositories.into_iter().map(|repo| repo.name));
+        if done {
+            break;
+        }
+    }
+
+    Ok(names)
+}
+
+#[async_trait]
+pub trait RunnerGroupExt {
+    async fn list_runner_groups(&self, org: &str) -> Result<Vec<GitHubRunnerGroup>>;
+    async fn get_runner_group(&self, org: &str, name: &str) -> Result<Option<GitHubRunnerGroup>>;
+    async fn create_runner_group(&self, org: &str, group_data: &RunnerGroupRequest) -> Result<GitHubRunnerGroup>;
+    async fn update_runner_group(&self, org: &str, group_id: u64, group_data: &RunnerGroupRequest) -> Result<GitHubRunnerGroup>;
+    async fn delete_runner_group(&self, org: &str, group_id: u64) -> Result<()>;
+    async fn list_runner_group_repositories(&self, org: &str, group_id: u64) -> Result<Vec<String>>;
+    async fn set_runner_group_repositories(
+        &self,
+        org: &str,
+        group_id: u64,
+        repos_data: &SetSelectedRepositoriesRequest,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+impl RunnerGroupExt for Octocrab {
+    async fn list_runner_groups(&self, org: &str) -> Result<Vec<GitHubRunnerGroup>> {
+        #[derive(serde::Serialize)]
+        struct PageQuery {
+            per_page: u8,
+            page: u32,
+        }
+
+        let route = format!("/orgs/{}/actions/runner-groups", org);
+        let mut groups = Vec::new();
+        for page in 1.. {
+            let response: GitHubRunnerGroups = self.get(&route, Some(&PageQuery { per_page: 100, page })).await?;
+            let done = response.runner_groups.len() < 100;
+            groups.extend(response.runner_groups);
+            if done {
+                break;
+            }
+        }
+
+        Ok(groups)
+    }
+
+    async fn get_runner_group(&self, org: &str, name: &str) -> Result<Option<GitHubRunnerGroup>> {
+        // Groups are addressed by id, so search the organization's groups for the name.
+        Ok(self
+            .list_runner_groups(org)
+            .await?
+            .into_iter()
+            .find(|group| group.name == name))
+    }
+
+    async fn create_runner_group(&self, org: &str, group_data: &RunnerGroupRequest) -> Result<GitHubRunnerGroup> {
+        let route = format!("/orgs/{}/actions/runner-groups", org);
+        self.post(route, Some(group_data)).await
+    }
+
+    async fn update_runner_group(&self, org: &str, group_id: u64, group_data: &RunnerGroupRequest) -> Result<GitHubRunnerGroup> {
+        let route = format!("/orgs/{}/actions/runner-groups/{}", org, group_id);
+        self.patch(route, Some(group_data)).await
+    }
+
+    async fn delete_runner_group(&self, org: &str, group_id: u64) -> Result<()> {
+        let route = format!("/orgs/{}/actions/runner-groups/{}", org, group_id);
+        self.delete(route, None::<&()>).await
+    }
+
+    async fn list_runner_group_repositories(&self, org: &str, group_id: u64) -> Result<Vec<String>> {
+        let route = format!("/orgs/{}/actions/runner-groups/{}/repositories", org, group_id);
+        list_selected_repositories(self, &route).await
+    }
+
+    async fn set_runner_group_repositories(
+        &self,
+        org: &str,
+        group_id: u64,
+        repos_data: &SetSelectedRepositoriesRequest,
+    ) -> Result<()> {
+        let route = format!("/orgs/{}/actions/runner-groups/{}/repositories", org, group_id);
+        self.put(route, Some(repos_data)).await
+    }
+}
+
+/// Run a GraphQL query, returning its `data` or bailing with the `errors` GitHub reported.
+/// GitHub answers GraphQL errors with a 200, so octocrab doesn't treat them as failures.
+async fn graphql_data(client: &Octocrab, query: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+    let mut response: serde_json::Value = client.graphql(query).await?;
+
+    if let Some(errors) = response.get("errors")
+        && !errors.is_null()
+    {
+        anyhow::bail!("GraphQL request failed: {}", errors);
+    }
+
+    Ok(response["data"].take())
+}
+
+/// Like `graphql_data`, but None when the request only failed because something it looked up doesn't exist.
+async fn graphql_data_if_found(client: &Octocrab, query: &serde_json::Value) -> anyhow::Result<Option<serde_json::Value>> {
+    let mut response: serde_json::Value = client.graphql(query).await?;
+
+    if let Some(errors) = response.get("errors")
+        && !errors.is_null()
+    {
+        if errors
+            .as_array()
+            .is_some_and(|errors| !errors.is_empty() && errors.iter().all(|error| error["type"] == "NOT_FOUND"))
+        {
+            return Ok(None);
+        }
+        anyhow::bail!("GraphQL request failed: {}", errors);
+    }
+
+    Ok(Some(response["data"].take()))
+}
+
+// GraphQL response structures for pattern-based branch protection rules
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubBranchRule {
+    pub id: String,
+    pub pattern: String,
+    pub requires_status_checks: bool,
+    pub requires_strict_status_checks: bool,
+    #[serde(default)]
+    pub required_status_check_contexts: Vec<String>,
+    pub is_admin_enforced: bool,
+    pub requires_approving_reviews: bool,
+    pub required_approving_review_count: Option<u32>,
+    pub dismisses_stale_reviews: bool,
+    pub requires_code_owner_reviews: bool,
+    pub require_last_push_approval: bool,
+    pub restricts_pushes: bool,
+    pub push_allowances: GitHubConnection<GitHubActorAllowance>,
+    pub restricts_review_dismissals: bool,
+    pub review_dismissal_allowances: GitHubConnection<GitHubActorAllowance>,
+    pub bypass_pull_request_allowances: GitHubConnection<GitHubActorAllowance>,
+    pub requires_linear_history: bool,
+    pub requires_commit_signatures: bool,
+    pub allows_force_pushes: bool,
+    pub allows_deletions: bool,
+    pub blocks_creations: bool,
+    pub requires_conversation_resolution: bool,
+    pub lock_branch: bool,
+    pub lock_allows_fetch_and_merge: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GitHubConnection<T> {
+    pub nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GitHubActorAllowance {
+    pub actor: Option<GitHubBranchRuleActor>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "__typename")]
+pub enum GitHubBranchRuleActor {
+    User { login: String },
+    Team { slug: String },
+    App { slug: String },
+    #[serde(other)]
+    Unknown,
+}
+
+const BRANCH_RULE_FIELDS: &str = "id pattern requiresStatusChecks requiresStrictStatusChecks requiredStatusCheckContexts \
+    isAdminEnforced requiresApprovingReviews requiredApprovingReviewCount dismissesStaleReviews requiresCodeOwnerReviews \
+    requireLastPushApproval restrictsPushes pushAllowances(first: 100) { nodes { actor { __typename \
+    ... on User { login } ... on Team { slug } ... on App { slug } } } } restrictsReviewDismissals \
+    reviewDismissalAllowances(first: 100) { nodes { actor { __typename ... on User { login } ... on Team { slug } \
+    ... on App { slug } } } } bypassPullRequestAllowances(first: 100) { nodes { actor { __typename \
+    ... on User { login } ... on Team { slug } ... on App { slug } } } } requiresLinearHistory requiresCommitSignatures \
+    allowsForcePushes allowsDeletions blocksCreations requiresConversationResolution lockBranch lockAllowsFetchAndMerge";
+
+#[derive(Debug, Deserialize)]
+pub struct GitHubNodeId {
+    pub node_id: String,
+}
+
+#[async_trait]
+pub trait BranchRuleExt {
+    async fn list_branch_rules(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<GitHubBranchRule>>;
+    async fn get_branch_rule(&self, owner: &str, repo: &str, pattern: &str) -> anyhow::Result<Option<GitHubBranchRule>>;
+    async fn create_branch_rule(&self, repository_id: &str, input: serde_json::Value) -> anyhow::Result<String>;
+    async fn update_branch_rule(&self, rule_id: &str, input: serde_json::Value) -> anyhow::Result<()>;
+    async fn delete_branch_rule(&self, rule_id: &str) -> anyhow::Result<()>;
+    async fn get_user_node_id(&self, login: &str) -> Result<GitHubNodeId>;
+    async fn get_team_node_id(&self, org: &str, team_slug: &str) -> Result<GitHubNodeId>;
+    async fn get_app_node_id(&self, app_slug: &str) -> Result<GitHubNodeId>;
+}
+
+#[async_trait]
+impl BranchRuleExt for Octocrab {
+    async fn list_branch_rules(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<GitHubBranchRule>> {
+        let mut rules = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let query = serde_json::json!({
+                "query": format!(
+                    "query($owner: String!, $name: String!, $after: String) {{ repository(owner: $owner, name: $name) {{ \
+                     branchProtectionRules(first: 100, after: $after) {{ nodes {{ {} }} pageInfo {{ hasNextPage endCursor }} }} }} }}",
+                    BRANCH_RULE_FIELDS
+                ),
+                "variables": { "owner": owner, "name": repo, "after": cursor },
+            });
+
+            let mut data = graphql_data(self, &query).await?;
+            let connection = &mut data["repository"]["branchProtectionRules"];
+            if connection.is_null() {
+                anyhow::bail!("GraphQL query for {}/{} returned no repository", owner, repo);
+            }
+
+            let page: Vec<GitHubBranchRule> = serde_json::from_value(connection["nodes"].take())?;
+            rules.extend(page);
+
+            if connection["pageInfo"]["hasNextPage"].as_bool() != Some(true) {
+                break;
+            }
+            cursor = connection["pageInfo"]["endCursor"].as_str().map(String::from);
+        }
+
+        Ok(rules)
+    }
+
+    async fn get_branch_rule(&self, owner: &str, repo: &str, pattern: &str) -> anyhow::Result<Option<GitHubBranchRule>> {
+        // There's no lookup by pattern, so page through only the rules' patterns until one matches,
+        // then fetch that rule on its own.
+        let mut cursor: Option<String> = None;
+
+        let rule_id = loop {
+            let query = serde_json::json!({
+                "query": "query($owner: String!, $name: String!, $after: String) { repository(owner: $owner, name: $name) { \
+                          branchProtectionRules(first: 100, after: $after) { nodes { id pattern } pageInfo { hasNextPage endCursor } } } }",
+                "variables": { "owner": owner, "name": repo, "after": cursor },
+            });
+
+            // A repository that doesn't exist has no rules.
+            let Some(data) = graphql_data_if_found(self, &query).await? else {
+                return Ok(None);
+            };
+            let connection = &data["repository"]["branchProtectionRules"];
+            if connection.is_null() {
+                anyhow::bail!("GraphQL query for {}/{} returned no repository", owner, repo);
+            }
+
+            let found = connection["nodes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find(|node| node["pattern"].as_str() == Some(pattern))
+                .and_then(|node| node["id"].as_str());
+            if let Some(id) = found {
+                break id.to_string();
+            }
+
+            if connection["pageInfo"]["hasNextPage"].as_bool() != Some(true) {
+                return Ok(None);
+            }
+            cursor = connection["pageInfo"]["endCursor"].as_str().map(String::from);
+        };
+
+        let query = serde_json::json!({
+            "query": format!("query($id: ID!) {{ node(id: $id) {{ ... on BranchProtectionRule {{ {} }} }} }}", BRANCH_RULE_FIELDS),
+            "variables": { "id": rule_id },
+        });
+
+        // The rule may have been deleted since its id was looked up.
+        let Some(mut data) = graphql_data_if_found(self, &query).await? else {
+            return Ok(None);
+        };
+        if data["node"].is_null() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_value(data["node"].take())?))
+    }
+
+    async fn create_branch_rule(&self, repository_id: &str, mut input: serde_json::Value) -> anyhow::Result<String> {
+        input["repositoryId"] = repository_id.into();
+
+        let query = serde_json::json!({
+            "query": "mutation($input: CreateBranchProtectionRuleInput!) { createBranchProtectionRule(input: $input) { branchProtectionRule { id } } }",
+            "variables": { "input": input },
+        });
+
+        let data = graphql_data(self, &query).await?;
+        match data["createBranchProtectionRule"]["branchProtectionRule"]["id"].as_str() {
+            Some(id) => Ok(id.to_string()),
+            None => anyhow::bail!("createBranchProtectionRule returned no rule"),
+        }
+    }
+
+    async fn update_branch_rule(&self, rule_id: &str, mut input: serde_json::Value) -> anyhow::Result<()> {
+        input["branchProtectionRuleId"] = rule_id.into();
+
+        let query = serde_json::json!({
+            "query": "mutation($input: UpdateBranchProtectionRuleInput!) { updateBranchProtectionRule(input: $input) { branchProtectionRule { id } } }",
+            "variables": { "input": input },
+        });
+
+        graphql_data(self, &query).await?;
+        Ok(())
+    }
+
+    async fn delete_branch_rule(&self, rule_id: &str) -> anyhow::Result<()> {
+        let query = serde_json::json!({
+            "query": "mutation($input: DeleteBranchProtectionRuleInput!) { deleteBranchProtectionRule(input: $input) { clientMutationId } }",
+            "variables": { "input": { "branchProtectionRuleId": rule_id } },
+        });
+
+        graphql_data(self, &query).await?;
+        Ok(())
+    }
+
+    async fn get_user_node_id(&self, login: &str) -> Result<GitHubNodeId> {
+        let route = format!("/users/{}", login);
+        self.get(route, None::<&()>).await
+    }
+
+    async fn get_team_node_id(&self, org: &str, team_slug: &str) -> Result<GitHubNodeId> {
+        let route = format!("/orgs/{}/teams/{}", org, team_slug);
+        self.get(route, None::<&()>).await
+    }
+
+    async fn get_app_node_id(&self, app_slug: &str) -> Result<GitHubNodeId> {
+        let route = format!("/apps/{}", app_slug);
+        self.get(route, None::<&()>).await
+    }
+}
@@ -1,24 +1,132 @@
 use autoschematic_core::connector::ConnectorOp;
 use serde::{Deserialize, Serialize};
 use autoschematic_core::util::RON;
+use std::collections::BTreeMap;
 
-use crate::resource::{CollaboratorPrincipal, Role};
+use crate::github_ext::UpdateRepositoryRequest;
+use crate::resource::{
+    ActionsAccessLevel, ActionsPermissions, Branch, CodeScanningDefaultSetup, CollaboratorPrincipal, CustomProperty,
+    CustomPropertyValue, ForkPrApproval, InteractionLimits, OidcSubjectClaim, OrgActionsPermissions, Pages, Role, RunnerGroup,
+};
 
 use super::resource::{GitHubRepository, BranchProtection};
 
+/// The state an op was planned from, checked again before the op runs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlannedState {
+    /// A fingerprint of the resource's state at plan time.
+    pub fingerprint: String,
+    /// Overrides the config's drift_check for this op: Some(true) always checks, Some(false) never does.
+    pub check: Option<bool>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum GitHubConnectorOp {
     CreateRepository(GitHubRepository),
-    UpdateRepository(GitHubRepository),
+    /// A patch of only the repository settings that changed.
+    UpdateRepository(UpdateRepositoryRequest),
     DeleteRepository,
+    /// Rename the repository at the same owner from the given previous name.
+    RenameRepository(String),
+    /// Transfer the repository from the given previous owner and name.
+    TransferRepository(String, String),
+    /// Archive the repository, making it read-only. Also planned in place of a delete under the archive deletion policy.
+    ArchiveRepository,
+    /// Unarchive the repository so that it can be changed again.
+    UnarchiveRepository,
+    /// Nothing to change: the creation-only options of a repository that already exists are ignored.
+    /// Planned so that the plan says so.
+    IgnoreCreationOptions,
+    /// Replace the repository's topics.
+    SetTopics(Vec<String>),
+    /// Rename a branch from the first name to the second. Used for the default branch, where GitHub
+    /// carries over open pull requests, protection and redirects.
+    RenameBranch(String, String),
+    /// Set the repository's custom property values. None unsets a property.
+    SetCustomPropertyValues(BTreeMap<String, Option<CustomPropertyValue>>),
 
     CreateBranchProtection(BranchProtection),
     UpdateBranchProtection(BranchProtection),
     DeleteBranchProtection,
 
+    /// Create the branch from its source, unless it already exists.
+    CreateBranch(Branch),
+    /// Delete the branch, unless it's already gone.
+    DeleteBranch,
+
+    CreateBranchRule(BranchProtection),
+    UpdateBranchRule(BranchProtection),
+    DeleteBranchRule,
+
+    /// Create or replace the organization's definitions of the given custom properties.
+    UpsertCustomProperties(BTreeMap<String, CustomProperty>),
+    /// Delete a custom property from the organization, along with its value on every repository.
+    DeleteCustomProperty(String),
+
+    /// Set which actions a repository may run, and whether Actions is enabled at all.
+    SetActionsPermissions(ActionsPermissions),
+    /// Set which repositories in the organization may use Actions, and which actions they may run.
+    SetOrgActionsPermissions(OrgActionsPermissions),
+    /// Set how many days workflow artifacts and logs are kept.
+    SetArtifactRetention(u32),
+    /// Set which outside contributors need approval before workflows run on their fork pull requests.
+    SetForkPrApproval(ForkPrApproval),
+    /// Set who outside the repository may use its actions and reusable workflows.
+    SetActionsAccessLevel(ActionsAccessLevel),
+    /// Set the template for the subject claim of OIDC tokens issued to workflows.
+    SetOidcSubjectClaim(OidcSubjectClaim),
+    /// Create a self-hosted runner group in the organization.
+    CreateRunnerGroup(RunnerGroup),
+    /// Update a runner group's settings and the repositories and workflows that may use it.
+    UpdateRunnerGroup(RunnerGroup),
+    /// Delete a runner group. Its runners move to the organization's default group.
+    DeleteRunnerGroup,
+    /// Enable GitHub Pages from the given source, then apply the rest of its settings.
+    CreatePages(Pages),
+    /// Update the Pages site's source, custom domain, HTTPS enforcement and visibility.
+    UpdatePages(Pages),
+    /// Disable GitHub Pages, unpublishing the site.
+    DeletePages,
+    /// Turn code scanning default setup on or off, waiting for its configuration run to finish.
+    SetCodeScanningDefaultSetup(CodeScanningDefaultSetup),
+    /// Apply an interaction limit, starting its expiry over.
+    SetInteractionLimits(InteractionLimits),
+    /// Lift the interaction limit before it expires.
+    RemoveInteractionLimits,
+
     AddCollaborator(CollaboratorPrincipal, Role),
     UpdateCollaborator(CollaboratorPrincipal, Role),
     RemoveCollaborator(CollaboratorPrincipal),
+
+    /// A branch protection op for the given branch, planned as part of its repository's plan so that it
+    /// runs between unarchiving and archiving the repository.
+    ForBranch(String, Box<GitHubConnectorOp>),
+    /// A branch rule op for the given pattern, planned as part of its repository's plan like `ForBranch`.
+    ForBranchRule(String, Box<GitHubConnectorOp>),
+
+    /// An op that only runs if the resource still matches the state it was planned from.
+    Guarded(PlannedState, Box<GitHubConnectorOp>),
+}
+
+impl GitHubConnectorOp {
+    /// Whether the op destroys something that can't be recreated from the workspace.
+    pub fn is_destructive(&self) -> bool {
+        match self {
+            GitHubConnectorOp::DeleteRepository
+            | GitHubConnectorOp::ArchiveRepository
+            | GitHubConnectorOp::DeleteBranchProtection
+            | GitHubConnectorOp::DeleteBranch
+            | GitHubConnectorOp::DeleteBranchRule
+            | GitHubConnectorOp::DeleteCustomProperty(_)
+            | GitHubConnectorOp::DeleteRunnerGroup
+            | GitHubConnectorOp::DeletePages
+            | GitHubConnectorOp::RemoveCollaborator(_) => true,
+            GitHubConnectorOp::ForBranch(_, op)
+            | GitHubConnectorOp::ForBranchRule(_, op)
+            | GitHubConnectorOp::Guarded(_, op) => op.is_destructive(),
+            _ => false,
+        }
+    }
 }
 
 impl ConnectorOp for GitHubConnectorOp {
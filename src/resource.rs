@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use autoschematic_core::{
     connector::{Resource, ResourceAddress},
@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 
 use super::addr::GitHubResourceAddress;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
 #[serde(default, deny_unknown_fields)]
 /// A GitHub repository with its configuration settings
 pub struct GitHubRepository {
@@ -46,8 +46,65 @@ pub struct GitHubRepository {
     pub archived: bool,
     /// Whether the repository is disabled
     pub disabled: bool,
+    /// Whether the repository is a template that other repositories can be generated from
+    pub is_template: bool,
+    /// Create the repository as a fork of this upstream repository, given as "owner/repo"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fork_of: Option<String>,
+    /// When forking, only copy the upstream's default branch. Only used on creation
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub fork_default_branch_only: bool,
     /// Map of collaborators (users or teams) and their permission roles
     pub collaborators: HashMap<CollaboratorPrincipal, Role>,
+    /// Create the repository from a template repository. Only used on creation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<RepositoryTemplate>,
+    /// Whether to create an initial commit with an empty README. Only used on creation
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub auto_init: bool,
+    /// The name of a .gitignore template to apply (e.g., "Rust"). Only used on creation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gitignore_template: Option<String>,
+    /// The keyword of an open source license to apply (e.g., "mit"). Only used on creation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_template: Option<String>,
+    /// The repository this one was previously known as, given as "owner/repo". When set and this
+    /// repository doesn't exist yet, the old repository is renamed or transferred instead of creating a new one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moved_from: Option<String>,
+    /// The current name of the default branch, when default_branch should be reached by renaming it
+    /// rather than switching to another existing branch. Open pull requests, branch protection and
+    /// links to the old name follow the rename
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_branch_renamed_from: Option<String>,
+    /// Values of the organization's custom properties, keyed by property name. Checked against the
+    /// organization's custom_properties.ron when planning
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_properties: BTreeMap<String, CustomPropertyValue>,
+}
+
+impl GitHubRepository {
+    /// Whether any of the creation-only options (template, auto_init, gitignore_template, license_template,
+    /// fork_default_branch_only) are set.
+    pub fn has_creation_options(&self) -> bool {
+        self.template.is_some()
+            || self.auto_init
+            || self.gitignore_template.is_some()
+            || self.license_template.is_some()
+            || self.fork_default_branch_only
+    }
+
+    /// Reset the creation-only options, moved_from and default_branch_renamed_from. GitHub can't report
+    /// them back for an existing repository, so they must be left out when comparing against the live state.
+    pub fn clear_creation_options(&mut self) {
+        self.template = None;
+        self.auto_init = false;
+        self.gitignore_template = None;
+        self.license_template = None;
+        self.fork_default_branch_only = false;
+        self.moved_from = None;
+        self.default_branch_renamed_from = None;
+    }
 }
 
 impl Default for GitHubRepository {
@@ -68,19 +125,80 @@ impl Default for GitHubRepository {
             default_branch: "main".into(),
             archived: false,
             disabled: false,
+            is_template: false,
+            fork_of: None,
+            fork_default_branch_only: false,
             collaborators: Default::default(),
+            template: None,
+            auto_init: false,
+            gitignore_template: None,
+            license_template: None,
+            moved_from: None,
+            default_branch_renamed_from: None,
+            custom_properties: BTreeMap::new(),
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// A template repository to generate a new repository from
+pub struct RepositoryTemplate {
+    /// The template repository, as "owner/repo"
+    pub repository: String,
+    /// Whether to include all branches from the template, not just the default branch
+    #[serde(default)]
+    pub include_all_branches: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
 #[serde(deny_unknown_fields)]
 /// Required status checks that must pass before merging a pull request
 pub struct RequiredStatusChecks {
     /// Whether to require branches to be up to date before merging
     pub strict: bool,
-    /// The list of status checks that must pass before branches can be merged
+    /// The list of status checks that must pass before branches can be merged, reported by any app
     pub contexts: Vec<String>,
+    /// Status checks that must be reported by a specific GitHub App
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<StatusCheck>,
+}
+
+impl RequiredStatusChecks {
+    /// Leave out the app slugs that can be compared without resolving them through the API. Where both
+    /// sides pin the check with the same context by app_id, the app_ids are compared and the slugs ignored.
+    /// A slug on one side and only an app_id on the other is left as it is, so they compare unequal until
+    /// the slug is resolved.
+    pub fn clear_unresolved_apps(&mut self, other: &mut Self) {
+        for check in &mut self.checks {
+            let Some(other_check) = other
+                .checks
+                .iter_mut()
+                .find(|other_check| other_check.context == check.context)
+            else {
+                continue;
+            };
+
+            if check.app_id.is_some() && other_check.app_id.is_some() {
+                check.app = None;
+                other_check.app = None;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// A required status check that only counts when reported by a specific GitHub App
+pub struct StatusCheck {
+    /// The name of the status check
+    pub context: String,
+    /// The ID of the GitHub App that must report the check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<i64>,
+    /// The slug of the GitHub App that must report the check, resolved to `app_id` when planning
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
@@ -95,6 +213,33 @@ pub struct PullRequestReviewEnforcement {
     pub require_code_owner_reviews: bool,
     /// Whether to require approval of the most recent reviewable push
     pub require_last_push_approval: bool,
+    /// Who may dismiss pull request reviews. When unset, anyone with write access can dismiss them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dismissal_restrictions: Option<ActorAllowances>,
+    /// Who may merge pull requests without the required reviews
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bypass_pull_request_allowances: Option<ActorAllowances>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// A set of users, teams and GitHub Apps granted an exception on a protected branch
+pub struct ActorAllowances {
+    /// Usernames
+    #[serde(default)]
+    pub users: Vec<String>,
+    /// Team slugs
+    #[serde(default)]
+    pub teams: Vec<String>,
+    /// GitHub App slugs
+    #[serde(default)]
+    pub apps: Vec<String>,
+}
+
+impl ActorAllowances {
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty() && self.teams.is_empty() && self.apps.is_empty()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
@@ -103,9 +248,9 @@ pub struct PullRequestReviewEnforcement {
 pub struct BranchRestrictions {
     /// Users allowed to push to the branch
     pub users: Vec<String>,
-    /// Teams allowed to push to the branch
+    /// Slugs of teams allowed to push to the branch
     pub teams: Vec<String>,
-    /// GitHub Apps allowed to push to the branch
+    /// Slugs of GitHub Apps allowed to push to the branch
     pub apps: Vec<String>,
 }
 
@@ -135,6 +280,661 @@ pub struct BranchProtection {
     pub lock_branch: bool,
     /// Whether to allow users with push access to sync from upstream forks
     pub allow_fork_syncing: bool,
+    /// Whether commits pushed to the branch must have verified signatures
+    #[serde(default)]
+    pub required_signatures: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// A long-lived branch that should exist in the repository, such as develop or a release branch
+pub struct Branch {
+    /// The branch, tag or commit SHA to create the branch from. Only used when the branch is created;
+    /// once it exists, the branch is left wherever it has moved to
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// A repository's GitHub Pages site
+pub struct Pages {
+    /// How the site is built
+    pub source: PagesSource,
+    /// The custom domain the site is served from, such as "docs.example.com". GitHub can only serve it
+    /// once the domain's DNS points at GitHub Pages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_domain: Option<String>,
+    /// Whether to redirect HTTP requests to HTTPS. With a custom domain, this needs the domain's
+    /// certificate to have been issued
+    #[serde(default)]
+    pub https_enforced: bool,
+    /// Who can see the site. Private sites need GitHub Enterprise Cloud; if left out, the visibility is left as it is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<PagesVisibility>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented)]
+/// How a GitHub Pages site is built
+pub enum PagesSource {
+    /// Built by GitHub from a folder on a branch, where the path is either "/" or "/docs"
+    Branch { branch: String, path: String },
+    /// Built and deployed by a GitHub Actions workflow in the repository
+    Workflow,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented)]
+/// Who can see a GitHub Pages site
+pub enum PagesVisibility {
+    /// Anyone on the internet
+    Public,
+    /// Only people with read access to the repository
+    Private,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// A repository's code scanning default setup, where GitHub runs CodeQL without a workflow file
+pub struct CodeScanningDefaultSetup {
+    /// Whether default setup is turned on
+    pub state: CodeScanningState,
+    /// The CodeQL query suite to run. Ignored while default setup is off
+    #[serde(default)]
+    pub query_suite: QuerySuite,
+    /// The languages to analyze, such as "python" or "javascript-typescript". If left empty, GitHub
+    /// analyzes every supported language it finds in the repository
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+}
+
+impl CodeScanningDefaultSetup {
+    /// Reset the settings that don't apply in the current configuration, so that they don't affect comparisons.
+    pub fn clear_unused(&mut self) {
+        match self.state {
+            CodeScanningState::NotConfigured => {
+                self.query_suite = QuerySuite::default();
+                self.languages.clear();
+            }
+            CodeScanningState::Configured => self.languages.sort(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented)]
+/// Whether code scanning default setup is turned on
+pub enum CodeScanningState {
+    /// Default setup is on, and GitHub scans the repository with CodeQL
+    Configured,
+    /// Default setup is off
+    NotConfigured,
+}
+
+impl CodeScanningState {
+    pub fn to_string(&self) -> String {
+        match self {
+            CodeScanningState::Configured => "configured",
+            CodeScanningState::NotConfigured => "not-configured",
+        }
+        .into()
+    }
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "configured" => Some(CodeScanningState::Configured),
+            "not-configured" => Some(CodeScanningState::NotConfigured),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Documented)]
+/// The CodeQL query suite run by code scanning default setup
+pub enum QuerySuite {
+    /// The queries with few false positives
+    #[default]
+    Default,
+    /// The default queries, plus lower-severity and lower-precision ones
+    Extended,
+}
+
+impl QuerySuite {
+    pub fn to_string(&self) -> String {
+        match self {
+            QuerySuite::Default => "default",
+            QuerySuite::Extended => "extended",
+        }
+        .into()
+    }
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "extended" => QuerySuite::Extended,
+            _ => QuerySuite::Default,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// A temporary limit on who can comment, open issues and open pull requests, for a public repository or
+/// for every public repository in an organization. Once the limit expires, GitHub lifts it and the next
+/// plan applies it again, for another `expiry`
+pub struct InteractionLimits {
+    /// Who can still interact
+    pub limit: InteractionLimit,
+    /// How long the limit lasts once applied. GitHub only reports when the limit ends, so this isn't compared
+    /// with the live limit
+    pub expiry: InteractionLimitExpiry,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented)]
+/// Who can still interact with a repository while an interaction limit is in place
+pub enum InteractionLimit {
+    /// Only users whose accounts are more than 24 hours old
+    ExistingUsers,
+    /// Only users who have committed to the repository's default branch before
+    ContributorsOnly,
+    /// Only collaborators and members of the organization
+    CollaboratorsOnly,
+}
+
+impl InteractionLimit {
+    pub fn to_string(&self) -> String {
+        match self {
+            InteractionLimit::ExistingUsers => "existing_users",
+            InteractionLimit::ContributorsOnly => "contributors_only",
+            InteractionLimit::CollaboratorsOnly => "collaborators_only",
+        }
+        .into()
+    }
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "existing_users" => Some(InteractionLimit::ExistingUsers),
+            "contributors_only" => Some(InteractionLimit::ContributorsOnly),
+            "collaborators_only" => Some(InteractionLimit::CollaboratorsOnly),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Documented)]
+/// How long an interaction limit lasts once applied
+pub enum InteractionLimitExpiry {
+    /// 24 hours
+    #[default]
+    OneDay,
+    /// 3 days
+    ThreeDays,
+    /// 1 week
+    OneWeek,
+    /// 1 month
+    OneMonth,
+    /// 6 months
+    SixMonths,
+}
+
+impl InteractionLimitExpiry {
+    pub fn to_string(&self) -> String {
+        match self {
+            InteractionLimitExpiry::OneDay => "one_day",
+            InteractionLimitExpiry::ThreeDays => "three_days",
+            InteractionLimitExpiry::OneWeek => "one_week",
+            InteractionLimitExpiry::OneMonth => "one_month",
+            InteractionLimitExpiry::SixMonths => "six_months",
+        }
+        .into()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// The custom properties that an organization's repositories can set
+pub struct CustomProperties {
+    /// Property definitions, keyed by property name
+    pub properties: BTreeMap<String, CustomProperty>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// The definition of a custom repository property
+pub struct CustomProperty {
+    /// The type of value the property holds
+    pub value_type: CustomPropertyType,
+    /// Whether every repository must have a value. Required properties must also have a default_value
+    #[serde(default)]
+    pub required: bool,
+    /// The value repositories get when they don't set one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<CustomPropertyValue>,
+    /// A short description of the property
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The values a SingleSelect or MultiSelect property can take
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_values: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented)]
+/// The type of value a custom property holds
+pub enum CustomPropertyType {
+    /// Any string
+    String,
+    /// One of the property's allowed_values
+    SingleSelect,
+    /// Any number of the property's allowed_values
+    MultiSelect,
+    /// "true" or "false"
+    TrueFalse,
+}
+
+impl CustomPropertyType {
+    pub fn to_string(&self) -> String {
+        match self {
+            CustomPropertyType::String => "string",
+            CustomPropertyType::SingleSelect => "single_select",
+            CustomPropertyType::MultiSelect => "multi_select",
+            CustomPropertyType::TrueFalse => "true_false",
+        }
+        .into()
+    }
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "string" => Some(CustomPropertyType::String),
+            "single_select" => Some(CustomPropertyType::SingleSelect),
+            "multi_select" => Some(CustomPropertyType::MultiSelect),
+            "true_false" => Some(CustomPropertyType::TrueFalse),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented)]
+#[serde(untagged)]
+/// The value of a custom property: a list for MultiSelect properties, otherwise a single string
+pub enum CustomPropertyValue {
+    /// The value of a String, SingleSelect or TrueFalse property
+    Single(String),
+    /// The chosen values of a MultiSelect property
+    Multiple(Vec<String>),
+}
+
+impl CustomProperties {
+    /// Check a repository's custom property values against these definitions, describing each problem found.
+    pub fn check_values(&self, values: &BTreeMap<String, CustomPropertyValue>) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        for name in values.keys() {
+            if !self.properties.contains_key(name) {
+                problems.push(format!("{} isn't defined in the organization's custom_properties.ron", name));
+            }
+        }
+
+        for (name, property) in &self.properties {
+            let Some(value) = values.get(name) else {
+                // The repository would get the default, which couldn't then be unset to match.
+                if property.required {
+                    problems.push(format!("{} is required, so it must be set", name));
+                }
+                continue;
+            };
+
+            let chosen: Vec<&String> = match (&property.value_type, value) {
+                (CustomPropertyType::MultiSelect, CustomPropertyValue::Multiple(chosen)) => chosen.iter().collect(),
+                (CustomPropertyType::MultiSelect, CustomPropertyValue::Single(_)) => {
+                    problems.push(format!("{} is a MultiSelect property, so its value must be a list", name));
+                    continue;
+                }
+                (_, CustomPropertyValue::Multiple(_)) => {
+                    problems.push(format!(
+                        "{} is a {:?} property, so its value must be a single string",
+                        name, property.value_type
+                    ));
+                    continue;
+                }
+                (_, CustomPropertyValue::Single(chosen)) => vec![chosen],
+            };
+
+            match property.value_type {
+                CustomPropertyType::String => {}
+                CustomPropertyType::TrueFalse => {
+                    if !matches!(chosen[0].as_str(), "true" | "false") {
+                        problems.push(format!(
+                            "{} is a TrueFalse property, so {:?} must be \"true\" or \"false\"",
+                            name, chosen[0]
+                        ));
+                    }
+                }
+                CustomPropertyType::SingleSelect | CustomPropertyType::MultiSelect => {
+                    for value in chosen {
+                        if !property.allowed_values.contains(value) {
+                            problems.push(format!(
+                                "{:?} isn't an allowed value of {} (allowed: {:?})",
+                                value, name, property.allowed_values
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// GitHub Actions permissions for a repository
+pub struct ActionsPermissions {
+    /// Whether GitHub Actions is enabled for the repository
+    pub enabled: bool,
+    /// Which actions and reusable workflows may run. Ignored while Actions is disabled
+    #[serde(default)]
+    pub allowed_actions: AllowedActions,
+    /// The actions allowed when allowed_actions is Selected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_actions: Option<SelectedActions>,
+    /// The default permissions of the GITHUB_TOKEN given to workflows
+    pub workflow_permissions: WorkflowPermissions,
+}
+
+impl ActionsPermissions {
+    /// Reset the settings that don't apply in the current configuration, so that they don't affect comparisons.
+    pub fn clear_unused(&mut self) {
+        if !self.enabled {
+            self.allowed_actions = AllowedActions::All;
+        }
+        self.selected_actions = clear_unused_selected_actions(&self.allowed_actions, self.selected_actions.take());
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// GitHub Actions permissions for an organization, which its repositories can only restrict further
+pub struct OrgActionsPermissions {
+    /// The repositories that may use GitHub Actions
+    pub enabled_repositories: EnabledRepositories,
+    /// Which actions and reusable workflows may run. Ignored while Actions is disabled
+    #[serde(default)]
+    pub allowed_actions: AllowedActions,
+    /// The actions allowed when allowed_actions is Selected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_actions: Option<SelectedActions>,
+    /// The default permissions of the GITHUB_TOKEN given to workflows
+    pub workflow_permissions: WorkflowPermissions,
+}
+
+impl OrgActionsPermissions {
+    /// Reset the settings that don't apply in the current configuration, so that they don't affect comparisons.
+    pub fn clear_unused(&mut self) {
+        match &mut self.enabled_repositories {
+            EnabledRepositories::Disabled => self.allowed_actions = AllowedActions::All,
+            EnabledRepositories::Selected(repos) => repos.sort(),
+            EnabledRepositories::All => {}
+        }
+        self.selected_actions = clear_unused_selected_actions(&self.allowed_actions, self.selected_actions.take());
+    }
+}
+
+/// Selected actions only apply when allowed_actions is Selected, and default to allowing none.
+fn clear_unused_selected_actions(
+    allowed_actions: &AllowedActions,
+    selected_actions: Option<SelectedActions>,
+) -> Option<SelectedActions> {
+    match allowed_actions {
+        AllowedActions::Selected => Some(selected_actions.unwrap_or_default()),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented)]
+/// The repositories in an organization that may use GitHub Actions
+pub enum EnabledRepositories {
+    /// Every repository
+    All,
+    /// Only these repositories, by name
+    Selected(Vec<String>),
+    /// No repositories; Actions is disabled across the organization
+    Disabled,
+}
+
+impl EnabledRepositories {
+    pub fn to_string(&self) -> String {
+        match self {
+            EnabledRepositories::All => "all",
+            EnabledRepositories::Selected(_) => "selected",
+            EnabledRepositories::Disabled => "none",
+        }
+        .into()
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Documented)]
+/// Which GitHub Actions and reusable workflows may run
+pub enum AllowedActions {
+    /// Any action or reusable workflow
+    #[default]
+    All,
+    /// Only actions and reusable workflows defined in the same repository or organization
+    LocalOnly,
+    /// Local actions plus those allowed by selected_actions
+    Selected,
+}
+
+impl AllowedActions {
+    pub fn to_string(&self) -> String {
+        match self {
+            AllowedActions::All => "all",
+            AllowedActions::LocalOnly => "local_only",
+            AllowedActions::Selected => "selected",
+        }
+        .into()
+    }
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "local_only" => AllowedActions::LocalOnly,
+            "selected" => AllowedActions::Selected,
+            _ => AllowedActions::All,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// The actions and reusable workflows allowed to run, on top of local ones
+pub struct SelectedActions {
+    /// Whether actions created by GitHub, in the actions and github organizations, are allowed
+    #[serde(default)]
+    pub github_owned_allowed: bool,
+    /// Whether actions from GitHub Marketplace verified creators are allowed
+    #[serde(default)]
+    pub verified_allowed: bool,
+    /// Patterns of allowed actions and reusable workflows, such as "monalisa/octocat@*" or "docker/*"
+    #[serde(default)]
+    pub patterns_allowed: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// The default permissions of the GITHUB_TOKEN given to workflows
+pub struct WorkflowPermissions {
+    /// Whether the token can read only, or read and write, repository contents and packages by default
+    pub default_workflow_permissions: WorkflowTokenPermissions,
+    /// Whether workflows can create and approve pull requests
+    pub can_approve_pull_request_reviews: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented)]
+/// The default access level of a workflow's GITHUB_TOKEN
+pub enum WorkflowTokenPermissions {
+    /// Read access to contents and packages
+    Read,
+    /// Read and write access
+    Write,
+}
+
+impl WorkflowTokenPermissions {
+    pub fn to_string(&self) -> String {
+        match self {
+            WorkflowTokenPermissions::Read => "read",
+            WorkflowTokenPermissions::Write => "write",
+        }
+        .into()
+    }
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "write" => WorkflowTokenPermissions::Write,
+            _ => WorkflowTokenPermissions::Read,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// Less common GitHub Actions settings for a repository
+pub struct ActionsSettings {
+    /// How many days workflow artifacts and logs are kept
+    pub artifact_and_log_retention_days: u32,
+    /// Which outside contributors need approval before workflows run on their fork pull requests. Only
+    /// applies to public repositories
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_pr_approval: Option<ForkPrApproval>,
+    /// Who outside the repository may use its actions and reusable workflows. Only applies to private
+    /// and internal repositories
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_level: Option<ActionsAccessLevel>,
+    /// The template for the subject claim of OIDC tokens issued to workflows, which cloud trust policies match on
+    pub oidc_subject_claim: OidcSubjectClaim,
+}
+
+impl ActionsSettings {
+    /// Reset the settings that don't apply in the current configuration, so that they don't affect comparisons.
+    pub fn clear_unused(&mut self) {
+        if self.oidc_subject_claim.use_default {
+            self.oidc_subject_claim.include_claim_keys.clear();
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented)]
+/// Which outside contributors need approval before workflows run on their fork pull requests
+pub enum ForkPrApproval {
+    /// Only first-time contributors who are also new to GitHub
+    FirstTimeContributorsNewToGitHub,
+    /// Only first-time contributors
+    FirstTimeContributors,
+    /// All outside contributors
+    AllExternalContributors,
+}
+
+impl ForkPrApproval {
+    pub fn to_string(&self) -> String {
+        match self {
+            ForkPrApproval::FirstTimeContributorsNewToGitHub => "first_time_contributors_new_to_github",
+            ForkPrApproval::FirstTimeContributors => "first_time_contributors",
+            ForkPrApproval::AllExternalContributors => "all_external_contributors",
+        }
+        .into()
+    }
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "first_time_contributors_new_to_github" => Some(ForkPrApproval::FirstTimeContributorsNewToGitHub),
+            "first_time_contributors" => Some(ForkPrApproval::FirstTimeContributors),
+            "all_external_contributors" => Some(ForkPrApproval::AllExternalContributors),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented)]
+/// Who outside a private or internal repository may use its actions and reusable workflows
+pub enum ActionsAccessLevel {
+    /// No one outside the repository
+    NoAccess,
+    /// Repositories owned by the same user
+    User,
+    /// Repositories in the same organization
+    Organization,
+    /// Repositories in the same enterprise
+    Enterprise,
+}
+
+impl ActionsAccessLevel {
+    pub fn to_string(&self) -> String {
+        match self {
+            ActionsAccessLevel::NoAccess => "none",
+            ActionsAccessLevel::User => "user",
+            ActionsAccessLevel::Organization => "organization",
+            ActionsAccessLevel::Enterprise => "enterprise",
+        }
+        .into()
+    }
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "none" => Some(ActionsAccessLevel::NoAccess),
+            "user" => Some(ActionsAccessLevel::User),
+            "organization" => Some(ActionsAccessLevel::Organization),
+            "enterprise" => Some(ActionsAccessLevel::Enterprise),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// The template for the subject claim of OIDC tokens issued to workflows
+pub struct OidcSubjectClaim {
+    /// Whether to use GitHub's default subject format (or the organization's template, if it has one)
+    pub use_default: bool,
+    /// The claims the subject is built from, in order, such as "repo" and "context". Required unless use_default is set
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_claim_keys: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Documented, DocumentedFields, FieldTypes)]
+#[serde(deny_unknown_fields)]
+/// A group of self-hosted runners in an organization, and which repositories and workflows may use it.
+/// The runners themselves register and leave on their own, so they aren't managed here
+pub struct RunnerGroup {
+    /// Which of the organization's repositories may use the group's runners
+    pub visibility: RunnerGroupVisibility,
+    /// Whether public repositories may use the group's runners
+    #[serde(default)]
+    pub allows_public_repositories: bool,
+    /// If set, only these workflows may use the group's runners, given as paths like
+    /// "octo-org/octo-repo/.github/workflows/deploy.yml@main"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restricted_to_workflows: Option<Vec<String>>,
+}
+
+impl RunnerGroup {
+    /// Sort the lists whose order GitHub doesn't keep, so that they don't affect comparisons.
+    pub fn clear_unused(&mut self) {
+        if let RunnerGroupVisibility::Selected(repos) = &mut self.visibility {
+            repos.sort();
+        }
+        if let Some(workflows) = &mut self.restricted_to_workflows {
+            workflows.sort();
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Documented)]
+/// The repositories in an organization that may use a runner group
+pub enum RunnerGroupVisibility {
+    /// Every repository
+    All,
+    /// Only these repositories, by name
+    Selected(Vec<String>),
+    /// Every private repository
+    Private,
+}
+
+impl RunnerGroupVisibility {
+    pub fn to_string(&self) -> String {
+        match self {
+            RunnerGroupVisibility::All => "all",
+            RunnerGroupVisibility::Selected(_) => "selected",
+            RunnerGroupVisibility::Private => "private",
+        }
+        .into()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Documented, DocumentedFields)]
@@ -198,6 +998,15 @@ impl Role {
 pub enum GitHubResource {
     Repository(GitHubRepository),
     BranchProtection(BranchProtection),
+    Branch(Branch),
+    CustomProperties(CustomProperties),
+    ActionsPermissions(ActionsPermissions),
+    OrgActionsPermissions(OrgActionsPermissions),
+    ActionsSettings(ActionsSettings),
+    RunnerGroup(RunnerGroup),
+    Pages(Pages),
+    CodeScanning(CodeScanningDefaultSetup),
+    InteractionLimits(InteractionLimits),
 }
 
 impl Resource for GitHubResource {
@@ -206,6 +1015,15 @@ impl Resource for GitHubResource {
         match self {
             GitHubResource::Repository(repo) => Ok(RON.to_string_pretty(&repo, pretty_config)?.into()),
             GitHubResource::BranchProtection(protection) => Ok(RON.to_string_pretty(&protection, pretty_config)?.into()),
+            GitHubResource::Branch(branch) => Ok(RON.to_string_pretty(&branch, pretty_config)?.into()),
+            GitHubResource::CustomProperties(properties) => Ok(RON.to_string_pretty(&properties, pretty_config)?.into()),
+            GitHubResource::ActionsPermissions(permissions) => Ok(RON.to_string_pretty(&permissions, pretty_config)?.into()),
+            GitHubResource::OrgActionsPermissions(permissions) => Ok(RON.to_string_pretty(&permissions, pretty_config)?.into()),
+            GitHubResource::ActionsSettings(settings) => Ok(RON.to_string_pretty(&settings, pretty_config)?.into()),
+            GitHubResource::RunnerGroup(group) => Ok(RON.to_string_pretty(&group, pretty_config)?.into()),
+            GitHubResource::Pages(pages) => Ok(RON.to_string_pretty(&pages, pretty_config)?.into()),
+            GitHubResource::CodeScanning(setup) => Ok(RON.to_string_pretty(&setup, pretty_config)?.into()),
+            GitHubResource::InteractionLimits(limits) => Ok(RON.to_string_pretty(&limits, pretty_config)?.into()),
         }
     }
 
@@ -219,7 +1037,165 @@ impl Resource for GitHubResource {
         match addr {
             GitHubResourceAddress::Repository { .. } => Ok(GitHubResource::Repository(RON.from_str(s)?)),
             GitHubResourceAddress::BranchProtection { .. } => Ok(GitHubResource::BranchProtection(RON.from_str(s)?)),
+            GitHubResourceAddress::Branch { .. } => Ok(GitHubResource::Branch(RON.from_str(s)?)),
+            GitHubResourceAddress::CustomProperties { .. } => Ok(GitHubResource::CustomProperties(RON.from_str(s)?)),
+            GitHubResourceAddress::ActionsPermissions { .. } => Ok(GitHubResource::ActionsPermissions(RON.from_str(s)?)),
+            GitHubResourceAddress::OrgActionsPermissions { .. } => Ok(GitHubResource::OrgActionsPermissions(RON.from_str(s)?)),
+            GitHubResourceAddress::ActionsSettings { .. } => Ok(GitHubResource::ActionsSettings(RON.from_str(s)?)),
+            GitHubResourceAddress::RunnerGroup { .. } => Ok(GitHubResource::RunnerGroup(RON.from_str(s)?)),
+            GitHubResourceAddress::Pages { .. } => Ok(GitHubResource::Pages(RON.from_str(s)?)),
+            GitHubResourceAddress::CodeScanning { .. } => Ok(GitHubResource::CodeScanning(RON.from_str(s)?)),
+            GitHubResourceAddress::InteractionLimits { .. } | GitHubResourceAddress::OrgInteractionLimits { .. } => {
+                Ok(GitHubResource::InteractionLimits(RON.from_str(s)?))
+            }
+            // Pattern rules have the same settings as protection on a single branch.
+            GitHubResourceAddress::BranchRule { .. } => Ok(GitHubResource::BranchProtection(RON.from_str(s)?)),
             _ => Err(invalid_addr(&addr)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(context: &str, app_id: Option<i64>, app: Option<&str>) -> StatusCheck {
+        StatusCheck {
+            context: context.to_string(),
+            app_id,
+            app: app.map(String::from),
+        }
+    }
+
+    fn status_checks(checks: Vec<StatusCheck>) -> RequiredStatusChecks {
+        RequiredStatusChecks {
+            strict: true,
+            contexts: Vec::new(),
+            checks,
+        }
+    }
+
+    #[test]
+    fn app_slug_differs_from_another_apps_id() {
+        // The slug would resolve to some other app's ID, which can't be known without the API.
+        let mut desired = status_checks(vec![check("ci/build", None, Some("octo-ci"))]);
+        let mut current = status_checks(vec![check("ci/build", Some(15368), None)]);
+
+        desired.clear_unresolved_apps(&mut current);
+        assert_ne!(desired, current);
+    }
+
+    #[test]
+    fn pinned_checks_pair_up_by_context() {
+        let mut desired = status_checks(vec![
+            check("ci/build", Some(15368), Some("github-actions")),
+            check("ci/lint", Some(42), None),
+        ]);
+        let mut current = status_checks(vec![check("ci/lint", Some(42), None)]);
+
+        desired.clear_unresolved_apps(&mut current);
+        assert_eq!(desired.checks[0].app, Some(String::from("github-actions")));
+        assert_ne!(desired, current);
+
+        let mut desired = status_checks(vec![check("ci/lint", Some(42), Some("octo-lint"))]);
+        let mut current = status_checks(vec![check("ci/lint", Some(42), None)]);
+
+        desired.clear_unresolved_apps(&mut current);
+        assert_eq!(desired, current);
+
+        let mut desired = status_checks(vec![check("ci/lint", None, None)]);
+        let mut current = status_checks(vec![check("ci/lint", Some(42), None)]);
+
+        desired.clear_unresolved_apps(&mut current);
+        assert_ne!(desired, current);
+    }
+
+    fn property(value_type: CustomPropertyType, required: bool, allowed_values: &[&str]) -> CustomProperty {
+        CustomProperty {
+            value_type,
+            required,
+            default_value: None,
+            description: None,
+            allowed_values: allowed_values.iter().map(|value| value.to_string()).collect(),
+        }
+    }
+
+    fn custom_properties() -> CustomProperties {
+        CustomProperties {
+            properties: BTreeMap::from([
+                (String::from("team"), property(CustomPropertyType::String, true, &[])),
+                (
+                    String::from("tier"),
+                    property(CustomPropertyType::SingleSelect, false, &["gold", "silver"]),
+                ),
+                (
+                    String::from("regions"),
+                    property(CustomPropertyType::MultiSelect, false, &["eu", "us"]),
+                ),
+                (String::from("public"), property(CustomPropertyType::TrueFalse, false, &[])),
+            ]),
+        }
+    }
+
+    fn single(value: &str) -> CustomPropertyValue {
+        CustomPropertyValue::Single(value.to_string())
+    }
+
+    fn multiple(values: &[&str]) -> CustomPropertyValue {
+        CustomPropertyValue::Multiple(values.iter().map(|value| value.to_string()).collect())
+    }
+
+    #[test]
+    fn custom_property_values_that_fit_their_definitions() {
+        let values = BTreeMap::from([
+            (String::from("team"), single("platform")),
+            (String::from("tier"), single("gold")),
+            (String::from("regions"), multiple(&["eu", "us"])),
+            (String::from("public"), single("false")),
+        ]);
+
+        assert!(custom_properties().check_values(&values).is_empty());
+    }
+
+    #[test]
+    fn custom_property_values_of_the_wrong_type() {
+        let values = BTreeMap::from([
+            (String::from("team"), multiple(&["platform"])),
+            (String::from("regions"), single("eu")),
+            (String::from("public"), single("yes")),
+        ]);
+
+        let problems = custom_properties().check_values(&values);
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert!(problems[0].starts_with("public is a TrueFalse property"));
+        assert!(problems[1].starts_with("regions is a MultiSelect property"));
+        assert!(problems[2].starts_with("team is a String property"));
+    }
+
+    #[test]
+    fn custom_property_values_outside_the_allowed_values() {
+        let values = BTreeMap::from([
+            (String::from("team"), single("platform")),
+            (String::from("tier"), single("bronze")),
+            (String::from("regions"), multiple(&["eu", "apac"])),
+        ]);
+
+        let problems = custom_properties().check_values(&values);
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].starts_with("\"apac\" isn't an allowed value of regions"));
+        assert!(problems[1].starts_with("\"bronze\" isn't an allowed value of tier"));
+    }
+
+    #[test]
+    fn missing_required_and_unknown_custom_properties() {
+        let values = BTreeMap::from([(String::from("owner"), single("platform"))]);
+
+        assert_eq!(
+            custom_properties().check_values(&values),
+            vec![
+                String::from("owner isn't defined in the organization's custom_properties.ron"),
+                String::from("team is required, so it must be set"),
+            ]
+        );
+    }
+}